@@ -1,16 +1,34 @@
 use std::any::Any;
-use std::collections::HashMap;
-use std::fmt::Debug;
+use std::collections::{HashMap, HashSet};
+use std::fmt::{Debug, Display, Formatter};
 use std::hash::{Hash, Hasher};
 use std::ops::Deref;
-use std::sync::Arc;
+use std::sync::{Arc, PoisonError, RwLock, RwLockReadGuard, RwLockWriteGuard};
+#[cfg(feature = "serde")]
+use std::io::BufReader;
+use std::{
+    fs::File,
+    io,
+    io::{BufWriter, Write},
+    path::Path,
+};
 
 use rayon::prelude::*;
-use robotics_lib::interface::{Direction, discover_tiles, one_direction_view, robot_map, robot_view};
+use robotics_lib::interface::{
+    discover_tiles, go, one_direction_view, robot_map, robot_view, Direction,
+};
 use robotics_lib::runner::Runnable;
 use robotics_lib::utils::LibError;
 use robotics_lib::world::tile::{Content, Tile, TileType};
 use robotics_lib::world::World;
+#[cfg(feature = "serde")]
+use serde::{ser::Error as _, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::pathfinder::{
+    active_teleports, changes_rocks, find_path, search, BobMode, Node, PathCost, PathError,
+    PlannerOptions,
+};
+use crate::utils::{get_edge_cost, manhattan_distance, match_content_type_variant};
 
 /// Enum that contains every possible pin type
 /// # Arguments
@@ -22,6 +40,8 @@ use robotics_lib::world::World;
 /// * `Bank(usize)`
 /// * `Market`
 /// * [`Custom(Arc<dyn Any>)`](BobPinTypes::Custom)
+///
+/// With the `serde` feature every pin type but [BobPinTypes::Custom] can be serialized
 /// # Examples
 /// ```
 /// use robotics_lib::world::tile::Content;
@@ -52,23 +72,31 @@ pub enum BobPinTypes {
     Custom(Arc<dyn Any + Send + Sync>),
 }
 
-/// ### Enum which says if the map has been updated
-/// it is used in the function [get_map](BobMap::get_map) to say
-/// if the map changed, if it didn't, the map won't try to auto_update
-/// saving time
-#[derive(PartialEq)]
-pub enum BobMapFlag{
-    NoTileUpdated,
-    TilesUpdated
-}
-
 /// enum that contains some specific errors
+///
+/// `PinAlreadySet` contains the first pin of the occupied tile, `OutOfBounds` is returned
+/// when the coordinates are outside the map
+#[derive(Debug)]
 pub enum BobErr{
-    PinAlreadySet,
+    PinAlreadySet(Arc<BobPinTypes>),
     PinNotFound,
-    EmptyTile
+    EmptyTile,
+    OutOfBounds
+}
+
+impl Display for BobErr {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BobErr::PinAlreadySet(pin) => write!(f, "pin {} already set at this tile.", pin),
+            BobErr::PinNotFound => write!(f, "pin not found on the map."),
+            BobErr::EmptyTile => write!(f, "there are no pins at this tile."),
+            BobErr::OutOfBounds => write!(f, "coordinates outside the map."),
+        }
+    }
 }
 
+impl std::error::Error for BobErr {}
+
 impl PartialEq<Self> for BobPinTypes {
     fn eq(&self, other: &Self) -> bool {
         match (self, other) {
@@ -138,24 +166,157 @@ impl Hash for BobPinTypes {
     }
 }
 
+/// Short human readable form of a pin, e.g. `Bank(3)` or `Contents(Rock(2))`
+///
+/// The type inside a [BobPinTypes::Custom] can't be known, so it's displayed as `Custom`
+impl Display for BobPinTypes {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BobPinTypes::I32(value) => write!(f, "I32({})", value),
+            BobPinTypes::String(value) => write!(f, "String({})", value),
+            BobPinTypes::TileType(tile_type) => write!(f, "TileType({:?})", tile_type),
+            BobPinTypes::Contents(content) => write!(f, "Contents({:?})", content),
+            BobPinTypes::City => write!(f, "City"),
+            BobPinTypes::Bank(value) => write!(f, "Bank({})", value),
+            BobPinTypes::Market => write!(f, "Market"),
+            BobPinTypes::Custom(_) => write!(f, "Custom"),
+        }
+    }
+}
+
+/// Serializable copy of a [BobPinTypes], [BobPinTypes::Custom] can't be saved
+#[cfg(feature = "serde")]
+#[derive(Serialize, Deserialize)]
+#[serde(rename = "BobPinTypes")]
+enum SavedPin {
+    I32(i32),
+    String(String),
+    TileType(TileType),
+    Contents(Content),
+    City,
+    Bank(usize),
+    Market,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<&BobPinTypes> for SavedPin {
+    type Error = &'static str;
+
+    fn try_from(pin: &BobPinTypes) -> Result<Self, Self::Error> {
+        match pin {
+            BobPinTypes::I32(value) => Ok(SavedPin::I32(*value)),
+            BobPinTypes::String(value) => Ok(SavedPin::String(value.clone())),
+            BobPinTypes::TileType(value) => Ok(SavedPin::TileType(value.clone())),
+            BobPinTypes::Contents(value) => Ok(SavedPin::Contents(value.clone())),
+            BobPinTypes::City => Ok(SavedPin::City),
+            BobPinTypes::Bank(value) => Ok(SavedPin::Bank(*value)),
+            BobPinTypes::Market => Ok(SavedPin::Market),
+            BobPinTypes::Custom(_) => Err("custom pins can't be serialized"),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<SavedPin> for BobPinTypes {
+    fn from(pin: SavedPin) -> Self {
+        match pin {
+            SavedPin::I32(value) => BobPinTypes::I32(value),
+            SavedPin::String(value) => BobPinTypes::String(value),
+            SavedPin::TileType(value) => BobPinTypes::TileType(value),
+            SavedPin::Contents(value) => BobPinTypes::Contents(value),
+            SavedPin::City => BobPinTypes::City,
+            SavedPin::Bank(value) => BobPinTypes::Bank(value),
+            SavedPin::Market => BobPinTypes::Market,
+        }
+    }
+}
+
+/// Serializes every pin type but [BobPinTypes::Custom], which returns an error
+#[cfg(feature = "serde")]
+impl Serialize for BobPinTypes {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        SavedPin::try_from(self)
+            .map_err(S::Error::custom)?
+            .serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for BobPinTypes {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        SavedPin::deserialize(deserializer).map(BobPinTypes::from)
+    }
+}
+
+/// Serializable copy of a [BobMap], used by [save](BobMap::save) and [load](BobMap::load)
+#[cfg(feature = "serde")]
+#[derive(Serialize, Deserialize)]
+struct SavedBobMap {
+    tiles: Vec<Vec<Option<Tile>>>,
+    pins: Vec<((usize, usize), SavedPin)>,
+    notes: Vec<((usize, usize), String)>,
+}
+
 /// Enhanced map containing Tiles + Pins
 /// # Details
 /// Regarding pins the map will always be updated
 ///
 /// Regarding tiles, the map will be updated quickly if using
 /// the interfaces [bob_view] and [bob_one_direction_view], and a bit more
-/// slowly when it auto updates from calling [get_map](BobMap::get_map) with
-/// the [BobMapFlag::TilesUpdated] flag
+/// slowly when it auto updates from calling [refresh](BobMap::refresh)
 /// # Functionalities
 /// * [`init`](BobMap::init): initialize map
+/// * [`from_tiles`](BobMap::from_tiles): initialize map from a matrix of tiles
 /// * [`add_pin`](BobMap::add_pin): add a pin
+/// * [`add_pin_multi`](BobMap::add_pin_multi): add a pin to a tile which may have other pins
+/// * [`add_or_replace_pin`](BobMap::add_or_replace_pin): add a pin replacing the ones on the tile
+/// * [`add_custom_pin`](BobMap::add_custom_pin): add a custom pin from any value
+/// * [`get_tile`](BobMap::get_tile): get a tile from coordinates
+/// * [`get_tile_mut`](BobMap::get_tile_mut): get a mutable tile from coordinates
+/// * [`tiles_in_box`](BobMap::tiles_in_box): get every tile inside a rectangle
+/// * [`find_tiles`](BobMap::find_tiles): find every tile satisfying a predicate
 /// * [`get_pin`](BobMap::get_pin): get a pin from coordinates
+/// * [`get_pins`](BobMap::get_pins): get every pin from coordinates
+/// * [`get_custom_pin`](BobMap::get_custom_pin): get the value of a custom pin from coordinates
+/// * [`add_pin_with_note`](BobMap::add_pin_with_note): add a pin with a note
+/// * [`get_note`](BobMap::get_note): get the note of coordinates
 /// * [`get_map`](BobMap::get_map): get the map with pins
+/// * [`refresh`](BobMap::refresh): update the map from the world and get it
+/// * [`update_with_diff`](BobMap::update_with_diff): update tiles and get the changed ones
+/// * [`last_diff`](BobMap::last_diff): get the tiles changed by the last view
+/// * [`take_discovered`](BobMap::take_discovered): count the tiles discovered since the last call
+/// * [`discovered_count`](BobMap::discovered_count): count the discovered tiles
+/// * [`discovered_ratio`](BobMap::discovered_ratio): get the fraction of discovered tiles
 /// * [`delete_pin`](BobMap::delete_pin): delete a pin at coordinates
+/// * [`delete_pin_matching`](BobMap::delete_pin_matching): delete one of the pins at coordinates
+/// * [`move_pin`](BobMap::move_pin): move the pins at coordinates somewhere else
 /// * [`search_pin`](BobMap::search_pin): search a pin from pin
+/// * [`iter_pins`](BobMap::iter_pins): list every pin
+/// * [`count_pin`](BobMap::count_pin): count the locations of a pin
+/// * [`pin_type_histogram`](BobMap::pin_type_histogram): count the locations of every pin
+/// * [`nearest_pin`](BobMap::nearest_pin): find the nearest location of a pin
+/// * [`nearest_content`](BobMap::nearest_content): find the nearest location of a content
+/// * [`to_relative`](BobMap::to_relative): translate coordinates into `robot_view` indices
+/// * [`to_absolute`](BobMap::to_absolute): translate `robot_view` indices into coordinates
+/// * [`save`](BobMap::save): save the map to a file (requires the `serde` feature)
+/// * [`load`](BobMap::load): load a saved map (requires the `serde` feature)
+/// * [`export_pins_csv`](BobMap::export_pins_csv): export every pin to a CSV file
+/// * [`export_image`](BobMap::export_image): export the map to a PNG (requires the `image` feature)
+/// * [`find_path`](BobMap::find_path): find the cheapest path between two tiles
+/// * [`route_to_nearest_pin`](BobMap::route_to_nearest_pin): find the cheapest path to a pin
+/// * [`route_waypoints`](BobMap::route_waypoints): find a path through several waypoints
+/// * [`reachable_tiles`](BobMap::reachable_tiles): find every tile reachable with some energy
+/// * [`dimensions`](BobMap::dimensions) and [`contains`](BobMap::contains): get the size of
+///   the map and check coordinates against it
+/// * [`elevation_delta`](BobMap::elevation_delta): get the climb between adjacent tiles
+/// * [`iter`](BobMap::iter): iterate over every cell with its coordinates, tile and pin
+/// * [`exploration_frontier`](BobMap::exploration_frontier): find the discovered tiles next
+///   to undiscovered ones
 pub struct BobMap {
-    map: Vec<Vec<(Option<Tile>, Option<Arc<BobPinTypes>>)>>,
+    map: Vec<Vec<(Option<Tile>, Vec<Arc<BobPinTypes>>, Option<String>)>>,
     pins_location: HashMap<Arc<BobPinTypes>, Vec<(usize, usize)>>,
+    last_diff: Vec<(usize, usize)>,
+    discovered: usize,
 }
 
 impl BobMap {
@@ -165,176 +326,1402 @@ impl BobMap {
     /// filled by the **current discovered map** and **no pins**
     /// # Example
     /// ```
-    /// use robotics_lib::world::World;
+    /// use robotics_lib::world::World;
+    /// use bob_lib::enhanced_map::BobMap;
+    ///
+    /// let world: World;
+    /// let mut map = BobMap::init(&world);
+    /// ```
+    pub fn init(world: &World) -> BobMap {
+        BobMap::from_tiles(robot_map(world).unwrap())
+    }
+
+    /// Function to initialize the map from an already known matrix of tiles
+    ///
+    /// The map will contain the given tiles and **no pins**, it's useful when the
+    /// tiles don't come from the [World], for example when testing
+    /// # Example
+    /// ```
+    /// use robotics_lib::world::tile::Tile;
+    /// use bob_lib::enhanced_map::BobMap;
+    ///
+    /// let tiles: Vec<Vec<Option<Tile>>> = vec![vec![None; 4]; 4];
+    /// let mut map = BobMap::from_tiles(tiles);
+    /// ```
+    pub fn from_tiles(tiles: Vec<Vec<Option<Tile>>>) -> BobMap {
+        let map: Vec<Vec<(Option<Tile>, Vec<Arc<BobPinTypes>>, Option<String>)>> = tiles
+            .into_par_iter()
+            .map(|row| row.into_iter().map(|tile| (tile, vec![], None)).collect())
+            .collect();
+        BobMap {
+            map,
+            pins_location: HashMap::new(),
+            last_diff: vec![],
+            discovered: 0,
+        }
+    }
+
+    fn update(&mut self, coordinates: Vec<(usize, usize, Tile)>) {
+        self.last_diff = self.update_with_diff(coordinates);
+    }
+
+    /// Function to update tiles of the map, recording which ones changed
+    ///
+    /// It returns a Vec containing the coordinates of the tiles whose value changed,
+    /// tiles which were already known with the same value are not included.
+    /// Tiles which were undiscovered are counted, see [take_discovered](BobMap::take_discovered).
+    /// Coordinates outside the map are ignored
+    /// # Example
+    /// ```
+    /// use robotics_lib::world::tile::Tile;
+    /// use bob_lib::enhanced_map::BobMap;
+    ///
+    /// let mut map: BobMap;
+    /// let tile: Tile;
+    /// let changed = map.update_with_diff(vec![(1, 3, tile)]);
+    /// ```
+    pub fn update_with_diff(
+        &mut self,
+        coordinates: Vec<(usize, usize, Tile)>,
+    ) -> Vec<(usize, usize)> {
+        let mut changed = vec![];
+        for (x, y, tile) in coordinates {
+            let Some((current, _, _)) = self.map.get_mut(x).and_then(|row| row.get_mut(y)) else {
+                continue;
+            };
+            if current.as_ref() != Some(&tile) {
+                if current.is_none() {
+                    self.discovered += 1;
+                }
+                *current = Some(tile);
+                changed.push((x, y));
+            }
+        }
+        changed
+    }
+
+    /// Function to get the tiles changed by the last update
+    ///
+    /// [bob_view], [bob_one_direction_view], [bob_radius_view] and [bob_discover_tiles]
+    /// update the map, after calling them this function returns the coordinates of the
+    /// tiles whose value actually changed, so only those need to be redrawn
+    /// # Example
+    /// ```
+    /// use robotics_lib::runner::Robot;
+    /// use robotics_lib::world::World;
+    /// use bob_lib::enhanced_map::{bob_view, BobMap};
+    ///
+    /// let world: World;
+    /// let robot: Robot;
+    /// let mut map: BobMap;
+    ///
+    /// bob_view(&robot, &world, &mut map);
+    /// for (x, y) in map.last_diff() {
+    ///     println!("({}, {}) changed", x, y);
+    /// }
+    /// ```
+    pub fn last_diff(&self) -> &Vec<(usize, usize)> {
+        &self.last_diff
+    }
+
+    /// Function to get how many tiles have been discovered since the last call
+    ///
+    /// Every tile which was undiscovered and has been revealed by [bob_view],
+    /// [bob_one_direction_view], [bob_radius_view], [bob_discover_tiles],
+    /// [refresh](BobMap::refresh) or [update_with_diff](BobMap::update_with_diff) is
+    /// counted once, the count is reset
+    /// by this function. It's used to update exploration goals, see
+    /// [GoalTracker::update_explored](crate::tracker::GoalTracker::update_explored)
+    /// # Example
+    /// ```
+    /// use bob_lib::enhanced_map::BobMap;
+    ///
+    /// let mut map: BobMap;
+    /// println!("{} new tiles", map.take_discovered());
+    /// ```
+    pub fn take_discovered(&mut self) -> usize {
+        std::mem::take(&mut self.discovered)
+    }
+
+    /// Function to get the size of the map as `(rows, cols)`
+    ///
+    /// Rows may have different lengths, `cols` is the length of the longest one
+    /// # Example
+    /// ```
+    /// use bob_lib::enhanced_map::BobMap;
+    ///
+    /// let map: BobMap;
+    /// let (rows, cols) = map.dimensions();
+    /// ```
+    pub fn dimensions(&self) -> (usize, usize) {
+        let cols = self.map.iter().map(Vec::len).max().unwrap_or(0);
+        (self.map.len(), cols)
+    }
+
+    /// Function to check if coordinates are inside the map
+    /// # Example
+    /// ```
+    /// use bob_lib::enhanced_map::BobMap;
+    ///
+    /// let map: BobMap;
+    /// if map.contains((3, 5)) {
+    ///     println!("{:?}", map.get_pin((3, 5)));
+    /// }
+    /// ```
+    pub fn contains(&self, (x, y): (usize, usize)) -> bool {
+        self.map.get(x).is_some_and(|row| y < row.len())
+    }
+
+    /// Function to count the discovered tiles of the map
+    /// # Example
+    /// ```
+    /// use bob_lib::enhanced_map::BobMap;
+    ///
+    /// let map: BobMap;
+    /// println!("{} tiles discovered", map.discovered_count());
+    /// ```
+    pub fn discovered_count(&self) -> usize {
+        self.map.iter().flatten().filter(|(tile, _, _)| tile.is_some()).count()
+    }
+
+    /// Function to get the fraction of the map which has been discovered
+    ///
+    /// It returns a value between `0.0` and `1.0`, `0.0` if the map is empty
+    /// # Example
+    /// ```
+    /// use bob_lib::enhanced_map::BobMap;
+    ///
+    /// let map: BobMap;
+    /// println!("{:.0}% explored", map.discovered_ratio() * 100.0);
+    /// ```
+    pub fn discovered_ratio(&self) -> f32 {
+        let total: usize = self.map.iter().map(|row| row.len()).sum();
+        if total == 0 {
+            return 0.0;
+        }
+        self.discovered_count() as f32 / total as f32
+    }
+
+    fn auto_update(&mut self, world: &World) {
+        let robot_map = robot_map(world).unwrap();
+        let m = self.get_mut_map();
+
+        // the tiles which were undiscovered are counted, like in update_with_diff
+        let discovered: usize = m
+            .par_iter_mut()
+            .enumerate()
+            .map(|(i, v)| {
+                let mut count = 0;
+                v.iter_mut().enumerate().for_each(|(j, (tile, _, _))| {
+                    // only clone the tiles which changed, rows may have different lengths
+                    if let Some(Some(new_tile)) = robot_map.get(i).and_then(|row| row.get(j)) {
+                        if tile.as_ref() != Some(new_tile) {
+                            if tile.is_none() {
+                                count += 1;
+                            }
+                            *tile = Some(new_tile.clone())
+                        }
+                    }
+                });
+                count
+            })
+            .sum();
+        self.discovered += discovered;
+    }
+
+    /// Function to add a pin to a location on the map
+    ///
+    /// It returns [Err] containing [BobErr::PinAlreadySet] with the pin already there if the
+    /// tile already has a pin, the map is left untouched. Use
+    /// [add_pin_multi](BobMap::add_pin_multi) to add more pins to the same tile or
+    /// [add_or_replace_pin](BobMap::add_or_replace_pin) to overwrite them
+    ///
+    /// It returns [Err] containing [BobErr::OutOfBounds] if the coordinates are outside the map
+    /// # Example
+    /// ```
+    /// use std::sync::Arc;
+    /// use robotics_lib::world;
+    /// use bob_lib::enhanced_map::{BobErr, BobMap, BobPinTypes};
+    ///
+    /// let mut map: BobMap;
+    /// if let Err(BobErr::PinAlreadySet(pin)) = map.add_pin(BobPinTypes::City, (1,3)) {
+    ///     println!("there is already a {} pin", pin);
+    /// }
+    /// ```
+    pub fn add_pin(&mut self, pin: BobPinTypes, (x, y): (usize, usize)) -> Result<(), BobErr>{
+        self.check_bounds((x, y))?;
+        if let Some(existing) = self.map[x][y].1.first() {
+            return Err(BobErr::PinAlreadySet(Arc::clone(existing)))
+        }
+        self.add_pin_multi(pin, (x, y))
+    }
+
+    /// Function to add a pin to a location on the map, replacing the pins already there
+    ///
+    /// The note of the location is kept
+    ///
+    /// It returns [Err] containing [BobErr::OutOfBounds] if the coordinates are outside the map
+    ///
+    /// It returns [Ok] containing the replaced pins, in the order they were added, which is
+    /// empty if the tile had no pins
+    /// # Example
+    /// ```
+    /// use bob_lib::enhanced_map::{BobMap, BobPinTypes};
+    ///
+    /// let mut map: BobMap;
+    /// let old = map.add_or_replace_pin(BobPinTypes::Market, (1, 3)).ok().unwrap();
+    /// ```
+    pub fn add_or_replace_pin(
+        &mut self,
+        pin: BobPinTypes,
+        (x, y): (usize, usize),
+    ) -> Result<Vec<Arc<BobPinTypes>>, BobErr> {
+        self.check_bounds((x, y))?;
+        let old = std::mem::take(&mut self.map[x][y].1);
+        for pin in old.iter() {
+            self.unindex_pin(pin, (x, y));
+        }
+        self.add_pin_multi(pin, (x, y))?;
+        Ok(old)
+    }
+
+    /// Function to add a [BobPinTypes::Custom] pin to a location on the map
+    ///
+    /// The value is wrapped in the [Arc] for you, retrieve it with
+    /// [get_custom_pin](BobMap::get_custom_pin)
+    ///
+    /// It returns [Err] containing [BobErr::PinAlreadySet] if the tile already has a pin
+    /// # Example
+    /// ```
+    /// use bob_lib::enhanced_map::BobMap;
+    ///
+    /// let mut map: BobMap;
+    /// map.add_custom_pin(vec![5.6, 2.1], (1, 3)).ok().unwrap()
+    /// ```
+    pub fn add_custom_pin<T: Send + Sync + 'static>(
+        &mut self,
+        value: T,
+        coordinates: (usize, usize),
+    ) -> Result<(), BobErr> {
+        self.add_pin(BobPinTypes::Custom(Arc::new(value)), coordinates)
+    }
+
+    /// Function to add a pin to a location on the map, even if it already has other pins
+    ///
+    /// It returns [Err] containing [BobErr::OutOfBounds] if the coordinates are outside the map
+    /// # Example
+    /// ```
+    /// use bob_lib::enhanced_map::{BobMap, BobPinTypes};
+    ///
+    /// let mut map: BobMap;
+    /// map.add_pin_multi(BobPinTypes::Market, (1,3)).ok().unwrap();
+    /// map.add_pin_multi(BobPinTypes::Bank(2), (1,3)).ok().unwrap();
+    /// ```
+    pub fn add_pin_multi(
+        &mut self,
+        pin: BobPinTypes,
+        (x, y): (usize, usize),
+    ) -> Result<(), BobErr> {
+        self.check_bounds((x, y))?;
+        let arc_pin = Arc::new(pin);
+        self.map[x][y].1.push(arc_pin.clone());
+        if self.pins_location.contains_key(&arc_pin) {
+            let vec = self.pins_location.get_mut(&arc_pin).unwrap();
+            vec.push((x, y));
+        } else {
+            self.pins_location.insert(arc_pin, vec![(x, y)]);
+        }
+        Ok(())
+    }
+
+    /// Function to add a pin with a note to a location on the map
+    ///
+    /// The note is a human readable string describing the location, it replaces the
+    /// previous note of the location if any
+    ///
+    /// It returns [Err] containing [BobErr::PinAlreadySet] if the tile already has a pin
+    /// # Example
+    /// ```
+    /// use bob_lib::enhanced_map::{BobMap, BobPinTypes};
+    ///
+    /// let mut map: BobMap;
+    /// let note = "sells fish cheap".to_string();
+    /// map.add_pin_with_note(BobPinTypes::Market, (1,3), note).ok().unwrap()
+    /// ```
+    pub fn add_pin_with_note(
+        &mut self,
+        pin: BobPinTypes,
+        (x, y): (usize, usize),
+        note: String,
+    ) -> Result<(), BobErr> {
+        self.add_pin(pin, (x, y))?;
+        self.map[x][y].2 = Some(note);
+        Ok(())
+    }
+
+    /// Function to retrieve the note of a location on the map
+    ///
+    /// It returns [None] if the location has no note
+    /// # Example
+    /// ```
+    /// use bob_lib::enhanced_map::BobMap;
+    ///
+    /// let map: BobMap;
+    /// if let Some(note) = map.get_note((1, 3)) {
+    ///     println!("{}", note);
+    /// }
+    /// ```
+    pub fn get_note(&self, (x, y): (usize, usize)) -> Option<&str> {
+        self.map.get(x)?.get(y)?.2.as_deref()
+    }
+
+    /// Function to retrieve a tile from the map
+    ///
+    /// It returns [None] if the coordinates are outside the map or the tile is undiscovered
+    /// # Example
+    /// ```
+    /// use bob_lib::enhanced_map::BobMap;
+    ///
+    /// let map: BobMap;
+    /// if let Some(tile) = map.get_tile((1, 3)) {
+    ///     println!("{:?}", tile.tile_type);
+    /// }
+    /// ```
+    pub fn get_tile(&self, (x, y): (usize, usize)) -> Option<&Tile> {
+        self.map.get(x)?.get(y)?.0.as_ref()
+    }
+
+    /// Function to retrieve a mutable reference to a tile of the map
+    ///
+    /// It returns [None] if the coordinates are outside the map or the tile is undiscovered
+    /// # Example
+    /// ```
+    /// use robotics_lib::world::tile::Content;
+    /// use bob_lib::enhanced_map::BobMap;
+    ///
+    /// let mut map: BobMap;
+    /// if let Some(tile) = map.get_tile_mut((1, 3)) {
+    ///     tile.content = Content::None;
+    /// }
+    /// ```
+    pub fn get_tile_mut(&mut self, (x, y): (usize, usize)) -> Option<&mut Tile> {
+        self.map.get_mut(x)?.get_mut(y)?.0.as_mut()
+    }
+
+    /// Function to get how much the elevation changes stepping from a tile onto an
+    /// adjacent one
+    ///
+    /// It returns [None] if one of the tiles is undiscovered or outside the map, or if the
+    /// tiles are not orthogonally adjacent
+    ///
+    /// It returns [Some] containing the elevation of `to` minus the one of `from`, positive
+    /// when climbing
+    /// # Example
+    /// ```
+    /// use bob_lib::enhanced_map::BobMap;
+    ///
+    /// let map: BobMap;
+    /// if let Some(climb) = map.elevation_delta((1, 3), (1, 4)).filter(|delta| *delta > 0) {
+    ///     println!("climbing {} meters", climb);
+    /// }
+    /// ```
+    pub fn elevation_delta(&self, from: (usize, usize), to: (usize, usize)) -> Option<i32> {
+        if manhattan_distance(from, to) != 1 {
+            return None;
+        }
+        let (from, to) = (self.get_tile(from)?, self.get_tile(to)?);
+        Some(to.elevation as i32 - from.elevation as i32)
+    }
+
+    /// Function to retrieve every discovered tile inside a rectangle of the map
+    ///
+    /// Both corners are included, the part of the rectangle outside the map is ignored.
+    /// The tiles are returned row by row, with their coordinates
+    /// # Example
+    /// ```
+    /// use bob_lib::enhanced_map::BobMap;
+    ///
+    /// let map: BobMap;
+    /// for ((x, y), tile) in map.tiles_in_box((2, 2), (5, 6)) {
+    ///     println!("({}, {}): {:?}", x, y, tile.tile_type);
+    /// }
+    /// ```
+    pub fn tiles_in_box(
+        &self,
+        (top, left): (usize, usize),
+        (bottom, right): (usize, usize),
+    ) -> Vec<((usize, usize), &Tile)> {
+        self.map
+            .iter()
+            .enumerate()
+            .take(bottom.saturating_add(1))
+            .skip(top)
+            .flat_map(|(x, row)| {
+                row.iter()
+                    .enumerate()
+                    .take(right.saturating_add(1))
+                    .skip(left)
+                    .filter_map(move |(y, (tile, _, _))| tile.as_ref().map(|tile| ((x, y), tile)))
+            })
+            .collect()
+    }
+
+    /// Function to find every discovered tile which satisfies a predicate
+    ///
+    /// The coordinates are returned row by row
+    /// # Example
+    /// ```
+    /// use robotics_lib::world::tile::TileType;
+    /// use bob_lib::enhanced_map::BobMap;
+    ///
+    /// let map: BobMap;
+    /// let lava = map.find_tiles(|tile| tile.tile_type == TileType::Lava);
+    /// ```
+    pub fn find_tiles(&self, pred: impl Fn(&Tile) -> bool) -> Vec<(usize, usize)> {
+        let mut found = vec![];
+        for (x, row) in self.map.iter().enumerate() {
+            for (y, (tile, _, _)) in row.iter().enumerate() {
+                if tile.as_ref().is_some_and(&pred) {
+                    found.push((x, y));
+                }
+            }
+        }
+        found
+    }
+
+    /// Function to find the exploration frontier, the discovered tiles next to at least one
+    /// undiscovered tile
+    ///
+    /// Only the tiles up, down, left and right are checked, the border of the map doesn't
+    /// make a tile part of the frontier. The coordinates are returned row by row
+    /// # Example
+    /// ```
+    /// use bob_lib::enhanced_map::BobMap;
+    /// use bob_lib::manhattan_distance;
+    ///
+    /// let map: BobMap;
+    /// let frontier = map.exploration_frontier();
+    /// let nearest = frontier.iter().min_by_key(|&&coord| manhattan_distance(coord, (4, 2)));
+    /// ```
+    pub fn exploration_frontier(&self) -> Vec<(usize, usize)> {
+        let undiscovered = |x: Option<usize>, y: Option<usize>| match (x, y) {
+            (Some(x), Some(y)) => {
+                matches!(self.map.get(x).and_then(|row| row.get(y)), Some((None, _, _)))
+            }
+            _ => false,
+        };
+
+        let mut frontier = vec![];
+        for (x, row) in self.map.iter().enumerate() {
+            for (y, (tile, _, _)) in row.iter().enumerate() {
+                if tile.is_some()
+                    && (undiscovered(Some(x), y.checked_add(1))
+                        || undiscovered(Some(x), y.checked_sub(1))
+                        || undiscovered(x.checked_sub(1), Some(y))
+                        || undiscovered(x.checked_add(1), Some(y)))
+                {
+                    frontier.push((x, y));
+                }
+            }
+        }
+        frontier
+    }
+
+    /// Function to retrieve a pin from a location on the map
+    ///
+    /// It returns [None] if there are no pins at the coordinates or they are outside the map
+    ///
+    /// It returns [Some] containing a pointer to the first [BobPinTypes] added to the tile
+    /// otherwise, use [get_pins](BobMap::get_pins) to get all of them
+    /// # Example
+    /// ```
+    /// use bob_lib::enhanced_map::BobMap;
+    ///
+    /// let mut map: BobMap;
+    /// let result = map.get_pin((1, 3));
+    /// ```
+    pub fn get_pin(&self, (x, y): (usize, usize)) -> Option<Arc<BobPinTypes>> {
+        self.map.get(x)?.get(y)?.1.first().cloned()
+    }
+
+    /// Function to retrieve every pin from a location on the map
+    ///
+    /// It returns a Vec containing pointers to the pins, in the order they were added,
+    /// which is empty if there are no pins at the coordinates or they are outside the map
+    /// # Example
+    /// ```
+    /// use bob_lib::enhanced_map::BobMap;
+    ///
+    /// let mut map: BobMap;
+    /// let pins = map.get_pins((1, 3));
+    /// ```
+    pub fn get_pins(&self, (x, y): (usize, usize)) -> Vec<Arc<BobPinTypes>> {
+        self.map.get(x).and_then(|row| row.get(y)).map_or(vec![], |(_, pins, _)| pins.clone())
+    }
+
+    /// Function to retrieve a [BobPinTypes::Custom] pin of the requested type from a
+    /// location on the map
+    ///
+    /// It returns [None] if none of the pins at the coordinates is a Custom of type `T`
+    /// # Example
+    /// ```
+    /// use bob_lib::enhanced_map::BobMap;
+    ///
+    /// let map: BobMap;
+    /// if let Some(values) = map.get_custom_pin::<Vec<f64>>((1, 3)) {
+    ///     println!("{:?}", values);
+    /// }
+    /// ```
+    pub fn get_custom_pin<T: Send + Sync + 'static>(
+        &self,
+        (x, y): (usize, usize),
+    ) -> Option<Arc<T>> {
+        self.map.get(x)?.get(y)?.1.iter().find_map(|pin| match pin.deref() {
+            BobPinTypes::Custom(value) => bob_type_check::<T>(Arc::clone(value)).ok(),
+            _ => None,
+        })
+    }
+
+    /// Function to delete a pin from a location on the map
+    ///
+    /// Every pin on the tile is deleted together with its note, use
+    /// [delete_pin_matching](BobMap::delete_pin_matching) to delete a single one
+    ///
+    /// It returns [Err] containing [BobErr::EmptyTile] if there are no pins at the coordinates
+    ///
+    /// It returns [Err] containing [BobErr::OutOfBounds] if the coordinates are outside the map
+    ///
+    /// It returns an empty [Ok] if the deletion was successful
+    /// # Example
+    /// ```
+    /// use bob_lib::enhanced_map::BobMap;
+    ///
+    /// let mut map: BobMap;
+    /// match map.delete_pin((1,3)) {
+    ///     Ok(_) => println!("deleted"),
+    ///     Err(_) => println!("nothing to delete")
+    /// }
+    /// ```
+    pub fn delete_pin(&mut self, (x, y): (usize, usize)) -> Result<(), BobErr> {
+        self.check_bounds((x, y))?;
+        if !self.map[x][y].1.is_empty() {
+            for pin in std::mem::take(&mut self.map[x][y].1) {
+                self.unindex_pin(&pin, (x, y));
+            }
+            self.map[x][y].2 = None;
+            return Ok(());
+        }
+        Err(BobErr::EmptyTile)
+    }
+
+    /// Function to delete the first pin matching a predicate from a location on the map
+    ///
+    /// It returns [Err] containing [BobErr::PinNotFound] if no pin at the coordinates
+    /// matches the predicate
+    ///
+    /// It returns [Err] containing [BobErr::OutOfBounds] if the coordinates are outside the map
+    ///
+    /// It returns [Ok] containing the deleted pin if the deletion was successful
+    /// # Example
+    /// ```
+    /// use bob_lib::enhanced_map::{BobMap, BobPinTypes};
+    ///
+    /// let mut map: BobMap;
+    /// // delete the market, keeping the other pins
+    /// let deleted = map.delete_pin_matching((1,3), |pin| *pin == BobPinTypes::Market);
+    /// ```
+    pub fn delete_pin_matching(
+        &mut self,
+        (x, y): (usize, usize),
+        predicate: impl Fn(&BobPinTypes) -> bool,
+    ) -> Result<Arc<BobPinTypes>, BobErr> {
+        self.check_bounds((x, y))?;
+        let Some(index) = self.map[x][y].1.iter().position(|pin| predicate(pin)) else {
+            return Err(BobErr::PinNotFound);
+        };
+        let pin = self.map[x][y].1.remove(index);
+        self.unindex_pin(&pin, (x, y));
+        Ok(pin)
+    }
+
+    /// Function to check that coordinates are inside the map
+    fn check_bounds(&self, coord: (usize, usize)) -> Result<(), BobErr> {
+        if self.contains(coord) {
+            Ok(())
+        } else {
+            Err(BobErr::OutOfBounds)
+        }
+    }
+
+    /// Function to remove a location from the index of a pin, the pin is removed from
+    /// the index when it has no locations left
+    fn unindex_pin(&mut self, pin: &Arc<BobPinTypes>, coordinates: (usize, usize)) {
+        if let Some(vec) = self.pins_location.get_mut(pin) {
+            if let Some(position) = vec.iter().position(|c| *c == coordinates) {
+                vec.remove(position);
+            }
+            if vec.is_empty() {
+                self.pins_location.remove(pin);
+            }
+        }
+    }
+
+    /// Function to move the pins of a location to another one
+    ///
+    /// The pins keep their identity, the same [Arc]s are moved and
+    /// [search_pin](BobMap::search_pin) will find them at the new location, the note
+    /// of the location is moved too
+    ///
+    /// It returns [Err] containing [BobErr::EmptyTile] if there are no pins at `from`
+    ///
+    /// It returns [Err] containing [BobErr::PinAlreadySet] if there are pins at `to`
+    ///
+    /// It returns [Err] containing [BobErr::OutOfBounds] if `from` or `to` are outside the map
+    ///
+    /// It returns an empty [Ok] if the pins were moved
+    /// # Example
+    /// ```
+    /// use bob_lib::enhanced_map::BobMap;
+    ///
+    /// let mut map: BobMap;
+    /// map.move_pin((1, 3), (1, 4)).ok().unwrap();
+    /// ```
+    pub fn move_pin(&mut self, from: (usize, usize), to: (usize, usize)) -> Result<(), BobErr> {
+        self.check_bounds(from)?;
+        self.check_bounds(to)?;
+        if self.map[from.0][from.1].1.is_empty() {
+            return Err(BobErr::EmptyTile);
+        }
+        if let Some(existing) = self.map[to.0][to.1].1.first() {
+            return Err(BobErr::PinAlreadySet(Arc::clone(existing)));
+        }
+
+        let pins = std::mem::take(&mut self.map[from.0][from.1].1);
+        for pin in pins.iter() {
+            if let Some(vec) = self.pins_location.get_mut(pin) {
+                if let Some(coordinates) = vec.iter_mut().find(|coordinates| **coordinates == from) {
+                    *coordinates = to;
+                }
+            }
+        }
+        self.map[to.0][to.1].1 = pins;
+        self.map[to.0][to.1].2 = self.map[from.0][from.1].2.take();
+        Ok(())
+    }
+
+    /// Function to get a full map with pins
+    ///
+    /// It returns a matrix of undiscovered and discovered Tiles, each associated with
+    /// their pins and their note
+    ///
+    /// The map is up to date if it was only updated through [bob_view],
+    /// [bob_one_direction_view], [add_pin](BobMap::add_pin), [bob_discover_tiles] or it
+    /// wasn't updated at all, otherwise use [refresh](BobMap::refresh)
+    /// # Example
+    /// ```
+    /// use bob_lib::enhanced_map::BobMap;
+    ///
+    /// let map: BobMap;
+    /// let enhanced_map = map.get_map();
+    /// ```
+    pub fn get_map(&self) -> &Vec<Vec<(Option<Tile>, Vec<Arc<BobPinTypes>>, Option<String>)>> {
+        self.map.as_ref()
+    }
+
+    /// Function to update the map with the tiles discovered by other means and get it
+    ///
+    /// If the map was updated by means different from our interfaces, e.g. another tool,
+    /// this function will auto update it from the [World], taking more time than
+    /// [get_map](BobMap::get_map)
+    /// # Example
+    /// ```
+    /// use robotics_lib::world::World;
+    /// use bob_lib::enhanced_map::BobMap;
+    ///
+    /// let mut map: BobMap;
+    /// let world: World;
+    /// let enhanced_map = map.refresh(&world);
+    /// ```
+    pub fn refresh(
+        &mut self,
+        world: &World,
+    ) -> &Vec<Vec<(Option<Tile>, Vec<Arc<BobPinTypes>>, Option<String>)>> {
+        self.auto_update(world);
+        self.map.as_ref()
+    }
+
+    fn get_mut_map(&mut self) -> &mut Vec<Vec<(Option<Tile>, Vec<Arc<BobPinTypes>>, Option<String>)>> {
+        self.map.as_mut()
+    }
+
+    /// Function to search a pin in the map
+    ///
+    /// It return [None] if the pin searched has not been placed
+    ///
+    /// It returns [Some] Containing a Vec of coordinates which all contain the specified
+    /// pin
+    /// # Example
+    /// ```
+    /// use std::sync::Arc;
+    /// use bob_lib::enhanced_map::{BobMap, BobPinTypes};
+    ///
+    /// let map: BobMap;
+    /// let coordinates = map.search_pin(BobPinTypes::Market);
+    /// ```
+    /// This function will keep in mind the value assigned to the enum, for example:
+    ///
+    /// ```
+    /// use std::sync::Arc;
+    /// use bob_lib::enhanced_map::{BobMap, BobPinTypes};
+    ///
+    /// let map: BobMap;
+    /// // these two will return different coordinates
+    /// let coordinates_1 = map.search_pin(BobPinTypes::I32(5));
+    /// let coordinates_2 = map.search_pin(BobPinTypes::I32(12));
+    /// ```
+    pub fn search_pin(&self, pin: BobPinTypes) -> Result<Vec<(usize, usize)>, BobErr> {
+        if self.pins_location.contains_key(&pin) {
+            let vec = self.pins_location.get(&pin).unwrap();
+            return Ok(vec.clone());
+        } else {
+            Err(BobErr::PinNotFound)
+        }
+    }
+
+    /// Function to save the map, tiles, pins and notes, to a file
+    ///
+    /// [BobPinTypes::Custom] pins can't be saved, they are skipped with a warning
+    ///
+    /// It returns [Err] containing an [io::Error] if the file can't be written
+    /// # Example
+    /// ```
+    /// use std::path::Path;
+    /// use bob_lib::enhanced_map::BobMap;
+    ///
+    /// let map: BobMap;
+    /// map.save(Path::new("bob_map.bin")).unwrap();
+    /// ```
+    #[cfg(feature = "serde")]
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let mut pins = vec![];
+        let mut notes = vec![];
+        for (x, row) in self.map.iter().enumerate() {
+            for (y, (_, tile_pins, note)) in row.iter().enumerate() {
+                if let Some(note) = note {
+                    notes.push(((x, y), note.clone()));
+                }
+                for pin in tile_pins {
+                    let Ok(saved) = SavedPin::try_from(pin.deref()) else {
+                        eprintln!("Warning: custom pin at ({}, {}) can't be saved, skipping it", x, y);
+                        continue;
+                    };
+                    pins.push(((x, y), saved));
+                }
+            }
+        }
+
+        let saved = SavedBobMap {
+            tiles: self.tiles(),
+            pins,
+            notes,
+        };
+        let writer = BufWriter::new(File::create(path)?);
+        bincode::serialize_into(writer, &saved).map_err(|e| io::Error::new(io::ErrorKind::Other, e))
+    }
+
+    /// Function to load a map saved with [save](BobMap::save)
+    ///
+    /// It returns [Err] containing an [io::Error] if the file can't be read or is not a
+    /// saved map, the error is [InvalidData](io::ErrorKind::InvalidData) if a pin or a note
+    /// is outside the saved tiles
+    /// # Example
+    /// ```
+    /// use std::path::Path;
+    /// use bob_lib::enhanced_map::BobMap;
+    ///
+    /// let map = BobMap::load(Path::new("bob_map.bin")).unwrap();
+    /// ```
+    #[cfg(feature = "serde")]
+    pub fn load(path: &Path) -> io::Result<BobMap> {
+        let reader = BufReader::new(File::open(path)?);
+        let saved: SavedBobMap = bincode::deserialize_from(reader)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+        let mut map = BobMap::from_tiles(saved.tiles);
+        for (coordinates, pin) in saved.pins {
+            map.add_pin_multi(pin.into(), coordinates)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        }
+        for ((x, y), note) in saved.notes {
+            let Some(tile) = map.map.get_mut(x).and_then(|row| row.get_mut(y)) else {
+                return Err(io::Error::new(io::ErrorKind::InvalidData, BobErr::OutOfBounds));
+            };
+            tile.2 = Some(note);
+        }
+        Ok(map)
+    }
+
+    /// Function to export every pin to a CSV file
+    ///
+    /// The file has a `x,y,pin_type,pin_value` header and a row for every pin, in the order
+    /// of [iter_pins](BobMap::iter_pins). The value of `City` and `Market` pins is empty,
+    /// `Custom` pins are skipped because their type can't be known
+    ///
+    /// It returns [Err] containing an [io::Error] if the file can't be written
+    /// # Example
+    /// ```
+    /// use std::path::Path;
+    /// use bob_lib::enhanced_map::BobMap;
+    ///
+    /// let map: BobMap;
+    /// map.export_pins_csv(Path::new("pins.csv")).unwrap();
+    /// ```
+    pub fn export_pins_csv(&self, path: &Path) -> io::Result<()> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        writeln!(writer, "x,y,pin_type,pin_value")?;
+
+        for ((x, y), pin) in self.iter_pins() {
+            let (pin_type, pin_value) = match pin.deref() {
+                BobPinTypes::I32(value) => ("I32", value.to_string()),
+                BobPinTypes::String(value) => ("String", value.clone()),
+                BobPinTypes::TileType(tile_type) => ("TileType", format!("{:?}", tile_type)),
+                BobPinTypes::Contents(content) => ("Contents", format!("{:?}", content)),
+                BobPinTypes::City => ("City", String::new()),
+                BobPinTypes::Bank(value) => ("Bank", value.to_string()),
+                BobPinTypes::Market => ("Market", String::new()),
+                BobPinTypes::Custom(_) => {
+                    eprintln!(
+                        "Warning: custom pin at ({}, {}) can't be exported, skipping it",
+                        x, y
+                    );
+                    continue;
+                }
+            };
+            writeln!(writer, "{},{},{},{}", x, y, pin_type, csv_field(&pin_value))?;
+        }
+
+        writer.flush()
+    }
+
+    /// Function to export the map to a PNG image, one pixel per tile
+    ///
+    /// Every tile is colored by its [TileType], tiles with pins are colored by the type of
+    /// their first pin instead and undiscovered tiles are transparent
+    ///
+    /// It returns [Err] containing an [io::Error] if the image can't be written
+    /// # Example
+    /// ```
+    /// use std::path::Path;
     /// use bob_lib::enhanced_map::BobMap;
     ///
-    /// let world: World;
-    /// let mut map = BobMap::init(&world);
+    /// let map: BobMap;
+    /// map.export_image(Path::new("bob_map.png")).unwrap();
     /// ```
-    pub fn init(world: &World) -> BobMap {
-        let robot_map = robot_map(world).unwrap();
-        let map: Vec<Vec<(Option<Tile>, Option<Arc<BobPinTypes>>)>> = robot_map
-            .into_par_iter()
-            .map(|row| row.into_iter().map(|tile| (tile, None)).collect())
-            .collect();
-        BobMap {
-            map,
-            pins_location: HashMap::new()
-        }
-    }
+    #[cfg(feature = "image")]
+    pub fn export_image(&self, path: &Path) -> io::Result<()> {
+        let height = self.map.len();
+        let width = self.map.iter().map(|row| row.len()).max().unwrap_or(0);
 
-    fn update(&mut self, coordinates: Vec<(usize, usize, Tile)>) {
-        for (x, y, tile) in coordinates {
-            self.map[x][y].0 = Some(tile);
+        let mut pixels = Vec::with_capacity(width * height * 4);
+        for row in self.map.iter() {
+            for (tile, pins, _) in row.iter() {
+                let color = match (tile, pins.first()) {
+                    (_, Some(pin)) => pin_color(pin),
+                    (Some(tile), None) => tile_type_color(&tile.tile_type),
+                    (None, None) => [0, 0, 0, 0],
+                };
+                pixels.extend_from_slice(&color);
+            }
+            // shorter rows are padded with transparent pixels
+            pixels.resize(pixels.len() + (width - row.len()) * 4, 0);
         }
+
+        let writer = BufWriter::new(File::create(path)?);
+        let mut encoder = png::Encoder::new(writer, width as u32, height as u32);
+        encoder.set_color(png::ColorType::Rgba);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header().map_err(io::Error::from)?;
+        writer.write_image_data(&pixels).map_err(io::Error::from)
     }
 
-    fn auto_update(&mut self, world: &World) {
-        let mut robot_map = robot_map(world).unwrap();
-        let mut m = self.get_mut_map();
+    /// Function to find the nearest location of a pin, by Manhattan distance
+    ///
+    /// Unlike [route_to_nearest_pin](BobMap::route_to_nearest_pin) it doesn't look for a
+    /// path, so it's much cheaper but the location may not be reachable
+    ///
+    /// It returns [None] if the pin has not been placed
+    ///
+    /// It returns [Some] containing the nearest coordinates and their distance, ties are
+    /// broken by the lowest coordinates
+    /// # Example
+    /// ```
+    /// use bob_lib::enhanced_map::{BobMap, BobPinTypes};
+    ///
+    /// let map: BobMap;
+    /// if let Some((coordinates, distance)) = map.nearest_pin((4, 2), BobPinTypes::Market) {
+    ///     println!("market at {:?}, {} tiles away", coordinates, distance);
+    /// }
+    /// ```
+    pub fn nearest_pin(
+        &self,
+        from: (usize, usize),
+        pin: BobPinTypes,
+    ) -> Option<((usize, usize), usize)> {
+        self.pins_location
+            .get(&pin)?
+            .iter()
+            .map(|&coord| (coord, manhattan_distance(coord, from)))
+            .min_by_key(|&(coordinates, distance)| (distance, coordinates))
+    }
 
-        m.par_iter_mut().enumerate().for_each(|(i, v)| {
-            v.iter_mut().enumerate().for_each(|(j, (tile, _))| {
-                if robot_map[i][j].is_some() {
-                    *tile = robot_map[i][j].clone()
-                }
-            })
+    /// Function to find the nearest discovered tile with a content, by Manhattan distance
+    ///
+    /// Only the variant of the content is checked, so `Content::Rock(0)` matches every
+    /// rock no matter its quantity
+    ///
+    /// It returns [None] if no discovered tile has the content
+    ///
+    /// It returns [Some] containing the nearest coordinates and their distance, ties are
+    /// broken by the lowest coordinates
+    /// # Example
+    /// ```
+    /// use robotics_lib::world::tile::Content;
+    /// use bob_lib::enhanced_map::BobMap;
+    ///
+    /// let map: BobMap;
+    /// if let Some((coordinates, distance)) = map.nearest_content((4, 2), &Content::Rock(0)) {
+    ///     println!("rocks at {:?}, {} tiles away", coordinates, distance);
+    /// }
+    /// ```
+    pub fn nearest_content(
+        &self,
+        from: (usize, usize),
+        content_variant: &Content,
+    ) -> Option<((usize, usize), usize)> {
+        self.find_tiles(|tile| {
+            match_content_type_variant(Some(tile.content.clone()), Some(content_variant.clone()))
         })
+        .into_iter()
+        .map(|coord| (coord, manhattan_distance(coord, from)))
+        .min_by_key(|&(coordinates, distance)| (distance, coordinates))
     }
 
-    /// Function to add a pin to a location on the map
+    /// Function to count how many tiles contain a pin
+    ///
+    /// Like [search_pin](BobMap::search_pin) it keeps in mind the value assigned to the
+    /// enum, so `Bank(3)` and `Bank(5)` are counted separately
     /// # Example
     /// ```
-    /// use std::sync::Arc;
-    /// use robotics_lib::world;
     /// use bob_lib::enhanced_map::{BobMap, BobPinTypes};
     ///
-    /// let mut map: BobMap;
-    /// map.add_pin(BobPinTypes::City, (1,3)).ok().unwrap()
+    /// let map: BobMap;
+    /// let markets = map.count_pin(&BobPinTypes::Market);
     /// ```
-    pub fn add_pin(&mut self, pin: BobPinTypes, (x, y): (usize, usize)) -> Result<(), BobErr>{
-        if self.map[x][y].1.is_some() {
-            return Err(BobErr::PinAlreadySet)
-        }
-        let arc_pin = Arc::new(pin);
-        self.map[x][y].1 = Some(arc_pin.clone());
-        if self.pins_location.contains_key(&arc_pin) {
-            let vec = self.pins_location.get_mut(&arc_pin).unwrap();
-            vec.push((x, y));
-        } else {
-            self.pins_location.insert(arc_pin.clone(), vec![(x, y)]);
-        }
-        Ok(())
+    pub fn count_pin(&self, pin: &BobPinTypes) -> usize {
+        self.pins_location.get(pin).map_or(0, |vec| vec.len())
     }
 
-    /// Function to retrieve a pin from a location on the map
-    ///
-    /// It returns [None] if there are no pins at the coordinates,
+    /// Function to count how many tiles contain each placed pin
     ///
-    /// It returns [Some] containing a pointer to a [BobPinTypes] otherwise
+    /// It returns a HashMap containing every placed pin and its count, see
+    /// [count_pin](BobMap::count_pin)
     /// # Example
     /// ```
     /// use bob_lib::enhanced_map::BobMap;
     ///
-    /// let mut map: BobMap;
-    /// let result = map.get_pin((1, 3));
+    /// let map: BobMap;
+    /// for (pin, count) in map.pin_type_histogram() {
+    ///     println!("{:?}: {}", pin, count);
+    /// }
     /// ```
-    pub fn get_pin(&self, (x, y): (usize, usize)) -> Option<Arc<BobPinTypes>> {
-        self.map[x][y].1.clone()
+    pub fn pin_type_histogram(&self) -> HashMap<BobPinTypes, usize> {
+        self.pins_location
+            .iter()
+            .map(|(pin, vec)| (pin.deref().clone(), vec.len()))
+            .collect()
     }
 
-    /// Function to delete a pin from a location on the map
+    /// Function to list every pin placed on the map
     ///
-    /// It returns [Err] containing [BobErr::EmptyTile] if there are no pins at the coordinates
-    ///
-    /// It returns an empty [Ok] if the deletion was successful
+    /// It returns a Vec containing the coordinates of every pin and a pointer to it,
+    /// sorted by coordinates, pins on the same tile are in the order they were added
     /// # Example
     /// ```
     /// use bob_lib::enhanced_map::BobMap;
     ///
-    /// let mut map: BobMap;
-    /// match map.delete_pin((1,3)) {
-    ///     Ok(_) => println!("deleted"),
-    ///     Err(_) => println!("nothing to delete")
+    /// let map: BobMap;
+    /// for ((x, y), pin) in map.iter_pins() {
+    ///     println!("({}, {}): {:?}", x, y, pin);
     /// }
     /// ```
-    pub fn delete_pin(&mut self, (x, y): (usize, usize)) -> Result<(), BobErr> {
-        if self.map[x][y].1.is_some() {
-            self.map[x][y].1 = None;
-            return Ok(());
-        }
-        Err(BobErr::EmptyTile)
+    pub fn iter_pins(&self) -> Vec<((usize, usize), Arc<BobPinTypes>)> {
+        let mut coordinates: Vec<(usize, usize)> =
+            self.pins_location.values().flatten().copied().collect();
+        coordinates.sort();
+        coordinates.dedup();
+
+        coordinates
+            .into_iter()
+            .flat_map(|(x, y)| self.map[x][y].1.iter().map(move |pin| ((x, y), pin.clone())))
+            .collect()
     }
 
-    /// Function to get a full map with pins
+    /// Function to iterate over every cell of the map without borrowing it mutably
     ///
-    /// It returns a matrix of undiscovered and discovered Tiles, each associated with
-    /// their pins
+    /// The cells are visited row by row, every item contains the coordinates of the cell,
+    /// its tile if it has been discovered and its first pin, see [get_pin](BobMap::get_pin)
+    /// # Example
+    /// ```
+    /// use bob_lib::enhanced_map::BobMap;
+    ///
+    /// let map: BobMap;
+    /// let discovered = map.iter().filter(|(_, _, tile, _)| tile.is_some()).count();
+    /// ```
+    pub fn iter(
+        &self,
+    ) -> impl Iterator<Item = (usize, usize, Option<&Tile>, Option<&Arc<BobPinTypes>>)> {
+        self.map.iter().enumerate().flat_map(|(x, row)| {
+            row.iter()
+                .enumerate()
+                .map(move |(y, (tile, pins, _))| (x, y, tile.as_ref(), pins.first()))
+        })
+    }
+
+    /// Function to translate absolute coordinates into the indices of the 3x3 matrix
+    /// returned by `robot_view`, where the robot is always at `(1, 1)`
     ///
-    /// If the map was updated by means different from our interfaces, the map will auto update
-    /// taking more time
+    /// It returns [None] if the coordinates are outside the 3x3 window around the robot
     /// # Example
-    /// if the map was only updated through [bob_view], [bob_one_direction_view]. [add_pin](BobMap::add_pin), [bob_discover_tile] or it wasn't updated at all
     /// ```
-    /// use robotics_lib::world::World;
-    /// use bob_lib::enhanced_map::{BobMap, BobMapFlag};
+    /// use robotics_lib::runner::Robot;
+    /// use bob_lib::enhanced_map::BobMap;
     ///
-    /// let mut map: BobMap;
-    /// let world: World;
-    /// let enhanced_map = map.get_map(&world, BobMapFlag::NoTileUpdated);
+    /// let robot: Robot;
+    /// let map: BobMap;
+    /// // the tile on the right of a robot in (4, 4)
+    /// assert_eq!(map.to_relative(&robot, (4, 5)), Some((1, 2)));
     /// ```
-    /// if the map was updated by different means
+    pub fn to_relative(
+        &self,
+        robot: &impl Runnable,
+        (x, y): (usize, usize),
+    ) -> Option<(usize, usize)> {
+        let (row, col) = (robot.get_coordinate().get_row(), robot.get_coordinate().get_col());
+        let rel_x = x.checked_add(1)?.checked_sub(row).filter(|rel_x| *rel_x < 3)?;
+        let rel_y = y.checked_add(1)?.checked_sub(col).filter(|rel_y| *rel_y < 3)?;
+        Some((rel_x, rel_y))
+    }
+
+    /// Function to translate the indices of the 3x3 matrix returned by `robot_view`
+    /// into absolute coordinates of the map
+    ///
+    /// It returns [None] if the indices are outside the 3x3 window or if they point
+    /// outside the map, e.g. on the left of a robot standing on the first column
+    /// # Example
     /// ```
-    /// use robotics_lib::world::World;
-    /// use bob_lib::enhanced_map::{BobMap, BobMapFlag};
+    /// use robotics_lib::runner::Robot;
+    /// use bob_lib::enhanced_map::BobMap;
     ///
-    /// let mut map: BobMap;
-    /// let world: World;
-    /// let enhanced_map = map.get_map(&world, BobMapFlag::TilesUpdated);
+    /// let robot: Robot;
+    /// let map: BobMap;
+    /// // the tile above a robot in (4, 4)
+    /// assert_eq!(map.to_absolute(&robot, (0, 1)), Some((3, 4)));
     /// ```
-    pub fn get_map(&mut self, world: &World, flag: BobMapFlag) -> &Vec<Vec<(Option<Tile>, Option<Arc<BobPinTypes>>)>> {
-        if flag == BobMapFlag::TilesUpdated {
-            self.auto_update(world);
+    pub fn to_absolute(
+        &self,
+        robot: &impl Runnable,
+        (rel_x, rel_y): (usize, usize),
+    ) -> Option<(usize, usize)> {
+        if rel_x >= 3 || rel_y >= 3 {
+            return None;
         }
-        self.map.as_ref()
+        let (row, col) = (robot.get_coordinate().get_row(), robot.get_coordinate().get_col());
+        let x = (row + rel_x).checked_sub(1).filter(|x| *x < self.map.len())?;
+        let y = (col + rel_y).checked_sub(1).filter(|y| *y < self.map[x].len())?;
+        Some((x, y))
     }
 
-    fn get_mut_map(&mut self) -> &mut Vec<Vec<(Option<Tile>, Option<Arc<BobPinTypes>>)>> {
-        self.map.as_mut()
+    /// Function to get the tiles of the map without pins
+    fn tiles(&self) -> Vec<Vec<Option<Tile>>> {
+        self.map
+            .par_iter()
+            .map(|row| row.iter().map(|(tile, _, _)| tile.clone()).collect())
+            .collect()
     }
 
-    /// Function to search a pin in the map
+    /// Function to find the cheapest path between two tiles of the map
     ///
-    /// It return [None] if the pin searched has not been placed
+    /// Only the discovered tiles are used to build the path, see
+    /// [find_path](crate::pathfinder::find_path) for the meaning of the options
+    /// and of the returned value
+    /// # Example
+    /// ```
+    /// use bob_lib::enhanced_map::BobMap;
+    /// use bob_lib::pathfinder::PlannerOptions;
     ///
-    /// It returns [Some] Containing a Vec of coordinates which all contain the specified
-    /// pin
+    /// let map: BobMap;
+    /// let options = PlannerOptions::energy_save().with_max_energy(100);
+    /// if let Ok((path, cost)) = map.find_path((0, 0), (3, 4), &options) {
+    ///     println!("{:?} costs {} energy and {} rocks", path, cost.energy, cost.material);
+    /// }
+    /// ```
+    pub fn find_path(
+        &self,
+        start: (usize, usize),
+        goal: (usize, usize),
+        options: &PlannerOptions,
+    ) -> Result<(Vec<(usize, usize)>, PathCost), PathError> {
+        let tiles = self.tiles();
+        find_path(&tiles, start, goal, options)
+    }
+
+    /// Function to find the cheapest path to the nearest tile with the given pin
+    ///
+    /// The path ends on the pinned tile if it's walkable, otherwise (e.g. a market or a bank)
+    /// on one of the tiles next to it, from where the robot can interact with the pin.
+    /// Every tile containing the pin is tried, the cheapest reachable one is chosen,
+    /// ties are broken by the lowest coordinates of the end of the path
+    ///
+    /// It returns [None] if the pin has not been placed or none of its tiles is reachable
+    ///
+    /// It returns [Some] containing the path to the pin, or to the tile next to it, and its
+    /// cost
     /// # Example
     /// ```
-    /// use std::sync::Arc;
     /// use bob_lib::enhanced_map::{BobMap, BobPinTypes};
+    /// use bob_lib::pathfinder::PlannerOptions;
     ///
     /// let map: BobMap;
-    /// let coordinates = map.search_pin(BobPinTypes::Market);
+    /// let options = PlannerOptions::energy_save().with_max_energy(100);
+    /// let route = map.route_to_nearest_pin((0, 0), BobPinTypes::Market, &options);
     /// ```
-    /// This function will keep in mind the value assigned to the enum, for example:
+    pub fn route_to_nearest_pin(
+        &self,
+        start: (usize, usize),
+        pin: BobPinTypes,
+        options: &PlannerOptions,
+    ) -> Option<(Vec<(usize, usize)>, PathCost)> {
+        let mut targets = self.search_pin(pin).ok()?;
+        targets.sort();
+
+        let tiles = self.tiles();
+        targets
+            .into_iter()
+            .flat_map(|(x, y)| {
+                let walkable = tiles[x][y].as_ref().and_then(get_edge_cost).is_some();
+                if walkable {
+                    return vec![(Some(x), Some(y))];
+                }
+                vec![
+                    (Some(x), y.checked_add(1)),
+                    (Some(x), y.checked_sub(1)),
+                    (x.checked_sub(1), Some(y)),
+                    (x.checked_add(1), Some(y)),
+                ]
+            })
+            .filter_map(|target| match target {
+                (Some(x), Some(y)) => find_path(&tiles, start, (x, y), options).ok(),
+                _ => None,
+            })
+            .min_by_key(|(path, cost)| (*cost, path.last().copied()))
+    }
+
+    /// Function to find every tile the robot can reach from `start` with `max_energy`
     ///
+    /// The discovered tiles are flooded from `start` following the cheapest steps of
+    /// [find_path](crate::pathfinder::find_path), climbing and active teleports included,
+    /// until the energy spent would exceed `max_energy`. The robot starts without rocks, so
+    /// tiles which cost rocks are reachable only with the rocks gained along the way
+    ///
+    /// It returns a HashSet containing the reachable coordinates, `start` included, it is
+    /// empty if `start` is outside the map
+    /// # Example
     /// ```
-    /// use std::sync::Arc;
-    /// use bob_lib::enhanced_map::{BobMap, BobPinTypes};
+    /// use bob_lib::enhanced_map::BobMap;
     ///
     /// let map: BobMap;
-    /// // these two will return different coordinates
-    /// let coordinates_1 = map.search_pin(BobPinTypes::I32(5));
-    /// let coordinates_2 = map.search_pin(BobPinTypes::I32(12));
+    /// let reachable = map.reachable_tiles((0, 0), 50);
     /// ```
-    pub fn search_pin(&self, pin: BobPinTypes) -> Result<Vec<(usize, usize)>, BobErr> {
-        if self.pins_location.contains_key(&pin) {
-            let vec = self.pins_location.get(&pin).unwrap();
-            return Ok(vec.clone());
-        } else {
-            Err(BobErr::PinNotFound)
+    pub fn reachable_tiles(
+        &self,
+        start: (usize, usize),
+        max_energy: usize,
+    ) -> HashSet<(usize, usize)> {
+        if self.check_bounds(start).is_err() {
+            return HashSet::new();
+        }
+
+        let tiles = self.tiles();
+        let teleports = active_teleports(&tiles);
+        // only the energy matters, whatever the rocks
+        let mode = BobMode::Weighted {
+            energy_weight: 1.0,
+            material_weight: 0.0,
+        };
+        let start_node = Node::new(start.0, start.1, max_energy as isize, 0);
+        // no node is the goal, so the whole reachable area is searched
+        let mut ret = HashSet::new();
+        search(
+            &start_node,
+            changes_rocks(&tiles),
+            |node| node.successors_with_teleports(&tiles, &mode, 1.0, &teleports),
+            |_| 0,
+            |node| {
+                ret.insert((node.x, node.y));
+                false
+            },
+        );
+        ret
+    }
+
+    /// Function to find a path visiting every waypoint in the given order
+    ///
+    /// The path of every leg is the cheapest one, consecutive legs share their
+    /// endpoint which appears only once in the returned path. Every leg starts with the
+    /// energy and rocks left by the previous ones
+    ///
+    /// It returns [None] as soon as one of the legs is unreachable
+    ///
+    /// It returns [Some] containing the whole path and the sum of the [PathCost] of the legs
+    /// # Example
+    /// ```
+    /// use bob_lib::enhanced_map::BobMap;
+    /// use bob_lib::pathfinder::PlannerOptions;
+    ///
+    /// let map: BobMap;
+    /// let waypoints = [(2, 3), (5, 1)];
+    /// let options = PlannerOptions::energy_save().with_max_energy(100);
+    /// let route = map.route_waypoints((0, 0), &waypoints, &options);
+    /// ```
+    pub fn route_waypoints(
+        &self,
+        start: (usize, usize),
+        waypoints: &[(usize, usize)],
+        options: &PlannerOptions,
+    ) -> Option<(Vec<(usize, usize)>, PathCost)> {
+        let tiles = self.tiles();
+        let mut path = vec![start];
+        let mut total_cost = PathCost::default();
+        let mut leg_options = options.clone().with_rocks(options.rocks.min(options.rock_capacity));
+
+        for waypoint in waypoints {
+            let from = *path.last().unwrap();
+            let (leg, cost) = find_path(&tiles, from, *waypoint, &leg_options).ok()?;
+            leg_options.max_energy -= cost.energy as isize;
+            leg_options.rocks -= cost.material;
+            path.extend(leg.into_iter().skip(1));
+            total_cost.energy += cost.energy;
+            total_cost.material += cost.material;
         }
+
+        Some((path, total_cost))
+    }
+}
+
+/// Struct that shares a [BobMap] between threads, e.g. a background thread updating the map
+/// while the main loop reads it
+///
+/// Cloning it gives another handle to the same map. The map is behind a [RwLock]: many
+/// threads can read it at the same time, a writer waits for every reader to finish. A
+/// thread which panicked while holding the lock doesn't make the map unusable, the map is
+/// handed out as it was left
+///
+/// To avoid deadlocks:
+/// * don't ask for a lock while holding another one on the same thread, e.g. calling
+///   [write](SharedBobMap::write) inside [with_read](SharedBobMap::with_read)
+/// * [refresh](BobMap::refresh) runs on the rayon thread pool while the write lock is held,
+///   so never lock the map from rayon tasks, they could wait for the refresh which waits
+///   for them
+/// * keep the guards short lived, e.g. clone the data needed instead of keeping the guard
+///   during a whole tick
+/// # Example
+/// ```
+/// use std::thread;
+/// use robotics_lib::world::World;
+/// use bob_lib::enhanced_map::{BobMap, SharedBobMap};
+///
+/// let world: World;
+/// let map = SharedBobMap::new(BobMap::init(&world));
+///
+/// let writer = map.clone();
+/// thread::spawn(move || writer.with_write(|map| map.refresh(&world).len()));
+/// let discovered = map.with_read(|map| map.discovered_count());
+/// ```
+#[derive(Clone)]
+pub struct SharedBobMap(Arc<RwLock<BobMap>>);
+
+impl SharedBobMap {
+    /// Function to share a map
+    pub fn new(map: BobMap) -> SharedBobMap {
+        SharedBobMap(Arc::new(RwLock::new(map)))
+    }
+
+    /// Function to lock the map for reading, the lock is released when the guard is dropped
+    pub fn read(&self) -> RwLockReadGuard<'_, BobMap> {
+        self.0.read().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Function to lock the map for writing, the lock is released when the guard is dropped
+    pub fn write(&self) -> RwLockWriteGuard<'_, BobMap> {
+        self.0.write().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Function to read the map, the lock is held only while `f` runs
+    pub fn with_read<R>(&self, f: impl FnOnce(&BobMap) -> R) -> R {
+        f(&self.read())
+    }
+
+    /// Function to change the map, the lock is held only while `f` runs
+    pub fn with_write<R>(&self, f: impl FnOnce(&mut BobMap) -> R) -> R {
+        f(&mut self.write())
+    }
+}
+
+impl From<BobMap> for SharedBobMap {
+    fn from(map: BobMap) -> Self {
+        SharedBobMap::new(map)
+    }
+}
+
+/// Function to quote a CSV field if it contains a separator, a quote or a newline
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// Function to get the RGBA color of a tile type in an exported image
+#[cfg(feature = "image")]
+fn tile_type_color(tile_type: &TileType) -> [u8; 4] {
+    match tile_type {
+        TileType::DeepWater => [0, 0, 139, 255],
+        TileType::ShallowWater => [65, 105, 225, 255],
+        TileType::Sand => [238, 214, 175, 255],
+        TileType::Grass => [34, 139, 34, 255],
+        TileType::Street => [128, 128, 128, 255],
+        TileType::Hill => [107, 142, 35, 255],
+        TileType::Mountain => [139, 115, 85, 255],
+        TileType::Snow => [255, 250, 250, 255],
+        TileType::Lava => [255, 69, 0, 255],
+        TileType::Teleport(_) => [148, 0, 211, 255],
+        TileType::Wall => [47, 47, 47, 255],
+    }
+}
+
+/// Function to get the RGBA color of a pin in an exported image
+#[cfg(feature = "image")]
+fn pin_color(pin: &BobPinTypes) -> [u8; 4] {
+    match pin {
+        BobPinTypes::I32(_) => [255, 0, 255, 255],
+        BobPinTypes::String(_) => [0, 255, 255, 255],
+        BobPinTypes::TileType(_) => [255, 255, 0, 255],
+        BobPinTypes::Contents(_) => [255, 128, 0, 255],
+        BobPinTypes::City => [255, 0, 0, 255],
+        BobPinTypes::Bank(_) => [255, 215, 0, 255],
+        BobPinTypes::Market => [0, 255, 0, 255],
+        BobPinTypes::Custom(_) => [255, 255, 255, 255],
     }
 }
 
@@ -342,6 +1729,9 @@ impl BobMap {
 ///
 /// It return a matrix 3x3 around the robot, containing the discovered tiles and the
 /// absolute coordinates relative to the map
+///
+/// When the robot is on the border of the map the tiles outside of it are skipped,
+/// so the matrix is smaller, e.g. 2x2 when the robot is at (0, 0)
 /// # Example
 /// ```
 /// use robotics_lib::runner::Robot;
@@ -365,23 +1755,70 @@ pub fn bob_view(
     let mut ret: Vec<Vec<(Option<Tile>, usize, usize)>> = vec![];
 
     for (i, v) in view.iter().enumerate() {
-        ret.push(vec![]);
+        // rows and columns outside the map are skipped, like robot_view does
+        let rows = map.map.len();
+        let Some(x) = (pos.get_row() + i).checked_sub(1).filter(|x| *x < rows) else {
+            continue;
+        };
+        let cols = map.map[x].len();
+        let mut row = vec![];
         for (j, tile) in v.iter().enumerate() {
-            let x = pos.get_row() - 1 + i;
-            let y = pos.get_col() - 1 + j;
+            let Some(y) = (pos.get_col() + j).checked_sub(1).filter(|y| *y < cols) else {
+                continue;
+            };
             if tile.is_some() {
                 update_vector.push((x, y, tile.clone().unwrap()));
-                ret[i].push((tile.clone(), x, y));
-            } else {
-                ret[i].push((tile.clone(), x, y));
             }
+            row.push((tile.clone(), x, y));
         }
+        ret.push(row);
     }
 
     map.update(update_vector);
     ret
 }
 
+/// The result of [go_and_track]: the view and the coordinates returned by [go], together with
+/// the view returned by [bob_view]
+pub type TrackedMove = (
+    (Vec<Vec<Option<Tile>>>, (usize, usize)),
+    Vec<Vec<(Option<Tile>, usize, usize)>>,
+);
+
+/// Function to replace the interface [go], keeping the map up to date
+///
+/// The robot moves with [go] and then looks around with [bob_view], so the tiles revealed
+/// around the new position are saved in the map
+///
+/// It returns an [Err] containing a [LibError] if the robot can't move, the map is
+/// left untouched
+///
+/// It returns [Ok] containing the [TrackedMove]: the view and the coordinates returned by [go],
+/// together with the view returned by [bob_view]
+/// # Example
+/// ```
+/// use robotics_lib::interface::Direction;
+/// use robotics_lib::runner::Robot;
+/// use robotics_lib::world::World;
+/// use bob_lib::enhanced_map::{go_and_track, BobMap};
+///
+/// let mut map: BobMap;
+/// let mut world: World;
+/// let mut robot: Robot;
+///
+/// let result = go_and_track(&mut robot, &mut world, Direction::Up, &mut map);
+/// ```
+pub fn go_and_track(
+    robot: &mut impl Runnable,
+    world: &mut World,
+    direction: Direction,
+    map: &mut BobMap,
+) -> Result<TrackedMove, LibError> {
+    let moved = go(robot, world, direction)?;
+    let view = bob_view(robot, world, map);
+    Ok((moved, view))
+}
+
 /// Function to replace the interface [one_direction_view]
 ///
 /// It returns an [Err] containing a [LibErr] if it fails
@@ -414,79 +1851,129 @@ pub fn bob_one_direction_view(
     let mut ret: Vec<Vec<(Tile, usize, usize)>> = vec![];
     let pos = robot.get_coordinate();
 
-    match direction {
-        Direction::Up => {
-            for (i, v) in long_view.iter().enumerate() {
-                ret.push(vec![]);
-                for (j, tile) in v.iter().enumerate() {
-                    let x = pos.get_row() - 1 - i;
-                    let y;
-                    if pos.get_col() == 0 {
-                        y = pos.get_col() + j;
-                    } else {
-                        y = pos.get_col() - 1 + j;
-                    }
-
-                    update_vector.push((x, y, tile.clone()));
-                    ret[i].push((tile.clone(), x, y));
-                }
-            }
-        }
-        Direction::Down => {
-            for (i, v) in long_view.iter().enumerate() {
-                ret.push(vec![]);
-                for (j, tile) in v.iter().enumerate() {
-                    let x = pos.get_row() + 1 + i;
-                    let y;
-                    if pos.get_col() == 0 {
-                        y = pos.get_col() + j;
-                    } else {
-                        y = pos.get_col() - 1 + j;
-                    }
+    // For Up and Down one_direction_view returns a row of tiles for every step away from
+    // the robot, the first one being adjacent to it: the i-th row is at row - 1 - i going
+    // Up and at row + 1 + i going Down. For Left and Right it returns the rows around the
+    // robot, each containing the tiles going away from it in the same way.
+    // The tiles across the direction are centered on the robot, unless it's on the first
+    // row or column of the map. Tiles which would be before the first row or column going
+    // Up or Left are skipped
+    let across = |center: usize, k: usize| if center == 0 { k } else { center - 1 + k };
+    let (row, col) = (pos.get_row(), pos.get_col());
 
-                    update_vector.push((x, y, tile.clone()));
-                    ret[i].push((tile.clone(), x, y));
-                }
-            }
-        }
-        Direction::Left => {
-            for (i, v) in long_view.iter().enumerate() {
-                ret.push(vec![]);
-                for (j, tile) in v.iter().enumerate() {
-                    let y = pos.get_col() - 1 - j;
-                    let x;
-                    if pos.get_row() == 0 {
-                        x = pos.get_row() + i;
-                    } else {
-                        x = pos.get_row() - 1 + i;
-                    }
-                    update_vector.push((x, y, tile.clone()));
-                    ret[i].push((tile.clone(), x, y));
-                }
-            }
-        }
-        Direction::Right => {
-            for (i, v) in long_view.iter().enumerate() {
-                ret.push(vec![]);
-                for (j, tile) in v.iter().enumerate() {
-                    let y = pos.get_col() + 1 + j;
-                    let x;
-                    if pos.get_row() == 0 {
-                        x = pos.get_row() + i;
-                    } else {
-                        x = pos.get_row() - 1 + i;
-                    }
-                    update_vector.push((x, y, tile.clone()));
-                    ret[i].push((tile.clone(), x, y));
-                }
-            }
+    for (i, v) in long_view.iter().enumerate() {
+        let mut row_tiles = vec![];
+        for (j, tile) in v.iter().enumerate() {
+            let coordinates = match direction {
+                Direction::Up => row.checked_sub(1 + i).map(|x| (x, across(col, j))),
+                Direction::Down => Some((row + 1 + i, across(col, j))),
+                Direction::Left => col.checked_sub(1 + j).map(|y| (across(row, i), y)),
+                Direction::Right => Some((across(row, i), col + 1 + j)),
+            };
+            let Some((x, y)) = coordinates else {
+                continue;
+            };
+            update_vector.push((x, y, tile.clone()));
+            row_tiles.push((tile.clone(), x, y));
         }
+        ret.push(row_tiles);
     }
 
     map.update(update_vector);
     Ok(ret)
 }
 
+/// Function to look at a (2 * radius + 1) x (2 * radius + 1) square around the robot
+///
+/// It calls [bob_view] and, if the radius is bigger than 1, [bob_one_direction_view] in
+/// every direction. Those only reveal a cross 3 tiles wide, so the tiles of the square
+/// still undiscovered are then revealed with [bob_discover_tiles]. The map is updated with
+/// every tile they discover
+///
+/// It returns an [Err] containing the [LibError] of the first view which fails, e.g. because
+/// the robot doesn't have enough energy, the tiles discovered before it are kept in the map
+///
+/// It returns [Ok] containing the square around the robot taken from the updated map, with
+/// the tiles and their absolute coordinates. The square is clamped on the borders of the map
+/// # Example
+/// ```
+/// use robotics_lib::runner::Robot;
+/// use robotics_lib::world::World;
+/// use bob_lib::enhanced_map::{bob_radius_view, BobMap};
+///
+/// let mut world: World;
+/// let mut robot: Robot;
+/// let mut map: BobMap;
+///
+/// let view = bob_radius_view(&mut robot, &mut world, 4, &mut map);
+/// ```
+pub fn bob_radius_view(
+    robot: &mut impl Runnable,
+    world: &mut World,
+    radius: usize,
+    map: &mut BobMap,
+) -> Result<Vec<Vec<(Option<Tile>, usize, usize)>>, LibError> {
+    bob_view(&*robot, world, map);
+    if radius > 1 {
+        // collect the changes of every view, otherwise only the last one would be kept
+        let mut diff = std::mem::take(&mut map.last_diff);
+        let result = look_around(robot, world, radius, map, &mut diff);
+        map.last_diff = diff;
+        result?;
+    }
+
+    let center = (robot.get_coordinate().get_row(), robot.get_coordinate().get_col());
+    let square = radius_square(map, center, radius);
+    Ok(square
+        .into_iter()
+        .map(|row| row.into_iter().map(|(x, y)| (map.map[x][y].0.clone(), x, y)).collect())
+        .collect())
+}
+
+/// Function to reveal the square of [bob_radius_view] beyond the tiles of [bob_view], the
+/// changes of every view are appended to `diff`
+fn look_around(
+    robot: &mut impl Runnable,
+    world: &mut World,
+    radius: usize,
+    map: &mut BobMap,
+    diff: &mut Vec<(usize, usize)>,
+) -> Result<(), LibError> {
+    for direction in [Direction::Up, Direction::Down, Direction::Left, Direction::Right] {
+        let result = bob_one_direction_view(robot, world, direction, radius, map);
+        diff.append(&mut map.last_diff);
+        result?;
+    }
+
+    let center = (robot.get_coordinate().get_row(), robot.get_coordinate().get_col());
+    let undiscovered: Vec<(usize, usize)> = radius_square(map, center, radius)
+        .into_iter()
+        .flatten()
+        .filter(|&(x, y)| map.map[x][y].0.is_none())
+        .collect();
+    if !undiscovered.is_empty() {
+        let result = bob_discover_tiles(robot, world, &undiscovered, map);
+        diff.append(&mut map.last_diff);
+        result?;
+    }
+    Ok(())
+}
+
+/// Function to get the coordinates of the square of [bob_radius_view], row by row, clamped
+/// on the borders of the map
+fn radius_square(
+    map: &BobMap,
+    (row, col): (usize, usize),
+    radius: usize,
+) -> Vec<Vec<(usize, usize)>> {
+    let rows = row.saturating_sub(radius)..(row + radius + 1).min(map.map.len());
+    rows.map(|x| {
+        let cols = col.saturating_sub(radius)..(col + radius + 1).min(map.map[x].len());
+        cols.map(|y| (x, y)).collect()
+    })
+    .collect()
+}
+
 /// Discovers tiles in the world based on specified coordinates and updates the BobMap.
 ///
 /// # Arguments
@@ -538,7 +2025,8 @@ pub fn bob_discover_tiles(robot: &mut impl Runnable,
 
 /// Function to check the type of a [BobPinTypes::Custom] after receiving it back
 ///
-/// It returns an empty [Err] if the Custom is not of the requested type
+/// It returns [Err] containing the same [Arc] it received if the Custom is not of the
+/// requested type, so it can be checked against another type
 ///
 /// It return [Ok] containing an [Arc] pointing to a value of the requested Type
 /// if the requested type is indeed the correct one
@@ -570,9 +2058,8 @@ pub fn bob_discover_tiles(robot: &mut impl Runnable,
 ///     _ => todo!()
 /// }
 /// ```
-pub fn bob_type_check<T: Send + Sync + 'static>(to_check: Arc<dyn Any + Send + Sync>) -> Result<Arc<T>, ()> {
-    if let Some(val) = to_check.downcast::<T>().ok() {
-        return Ok(val);
-    }
-    Err(())
+pub fn bob_type_check<T: Send + Sync + 'static>(
+    to_check: Arc<dyn Any + Send + Sync>,
+) -> Result<Arc<T>, Arc<dyn Any + Send + Sync>> {
+    to_check.downcast::<T>()
 }
\ No newline at end of file
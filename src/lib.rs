@@ -1,3 +1,14 @@
 pub mod tracker;
 pub mod enhanced_map;
-pub(crate) mod utils;
\ No newline at end of file
+pub mod navigation;
+pub mod pathfinder;
+pub mod render;
+pub mod robot;
+pub(crate) mod utils;
+
+pub use pathfinder::{BobMode, Node, PathCost, PlannerOptions};
+pub use utils::{
+    chebyshev_distance, costs_relation, direction_between, direction_in_view, find_direction_to,
+    get_diagonal_edge_cost, get_edge_cost, get_edge_cost_with_elevation, get_tile_in_direction,
+    manhattan_distance, match_content_type_variant, pretty_print_tilemap, tile_in_view_direction,
+};
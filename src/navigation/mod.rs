@@ -0,0 +1,257 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+use robotics_lib::interface::{go, look_at_sky, Direction};
+use robotics_lib::runner::Runnable;
+use robotics_lib::utils::LibError;
+use robotics_lib::world::tile::Content;
+use robotics_lib::world::World;
+
+use crate::enhanced_map::BobMap;
+use crate::pathfinder::{BobMode, PathCost, PathError, PlannerOptions};
+use crate::tracker::{destroy_and_collect_item, ActionError, GoalProgress, GoalTracker};
+use crate::utils::{direction_between, get_edge_cost};
+
+/// Enum that contains the reasons why walking a path can fail
+///
+/// Every variant contains the energy spent before the failure
+/// # Variants
+/// * `Lib` - The robot failed to move, contains the error returned by `go`
+/// * `NotAdjacent` - Two consecutive coordinates of the path are not adjacent
+#[derive(Debug)]
+pub enum WalkError {
+    Lib {
+        error: LibError,
+        energy_spent: usize,
+    },
+    NotAdjacent {
+        from: (usize, usize),
+        to: (usize, usize),
+        energy_spent: usize,
+    },
+}
+
+impl Display for WalkError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WalkError::Lib { error, energy_spent } => write!(
+                f,
+                "the robot failed to move after spending {} energy: {:?}.",
+                energy_spent, error
+            ),
+            WalkError::NotAdjacent { from, to, .. } => {
+                write!(f, "{:?} and {:?} are not adjacent.", from, to)
+            }
+        }
+    }
+}
+
+impl Error for WalkError {}
+
+impl WalkError {
+    /// Returns the energy spent walking before the failure
+    pub fn energy_spent(&self) -> usize {
+        match self {
+            WalkError::Lib { energy_spent, .. } => *energy_spent,
+            WalkError::NotAdjacent { energy_spent, .. } => *energy_spent,
+        }
+    }
+}
+
+/// Function to move the robot along a path, like the one returned by
+/// [find_path](crate::pathfinder::find_path)
+///
+/// The path is made of `(row, col)` coordinates, the first one being the robot position:
+/// every pair of consecutive coordinates is turned into a [Direction] and the robot
+/// moves with `go`, stopping at the first error
+///
+/// It returns [Err] containing a [WalkError] if the robot couldn't move or two
+/// consecutive coordinates are not adjacent
+///
+/// It returns [Ok] containing the energy spent if the robot reached the end of the path,
+/// it's read from the robot energy before and after every step so it can be compared
+/// with the cost predicted by the pathfinder
+/// # Example
+/// ```
+/// use robotics_lib::runner::Robot;
+/// use robotics_lib::world::World;
+/// use bob_lib::navigation::walk_path;
+///
+/// let mut robot: Robot;
+/// let mut world: World;
+/// let path = vec![(0, 0), (0, 1), (1, 1)];
+/// let energy_spent = walk_path(&mut robot, &mut world, &path).unwrap();
+/// ```
+pub fn walk_path(
+    robot: &mut impl Runnable,
+    world: &mut World,
+    path: &[(usize, usize)],
+) -> Result<usize, WalkError> {
+    let mut energy_spent = 0;
+
+    for step in path.windows(2) {
+        let (from, to) = (step[0], step[1]);
+        let Some(direction) = direction_between(from, to) else {
+            return Err(WalkError::NotAdjacent { from, to, energy_spent });
+        };
+
+        let energy_before = robot.get_energy().get_energy_level();
+        let result = go(robot, world, direction);
+        energy_spent += energy_before.saturating_sub(robot.get_energy().get_energy_level());
+
+        if let Err(error) = result {
+            return Err(WalkError::Lib { error, energy_spent });
+        }
+    }
+    Ok(energy_spent)
+}
+
+/// Function to estimate the cost of a step without moving the robot
+///
+/// The tile next to `from` in `direction` is looked up in the map and its
+/// [get_edge_cost](crate::get_edge_cost) is returned, climbing and the weather
+/// are not taken into account
+///
+/// It returns [None] if the tile is outside the map, undiscovered or can't be entered
+///
+/// It returns [Some] containing the `(energy, rocks)` cost of entering the tile, the rocks
+/// are negative if the step gives rocks
+/// # Example
+/// ```
+/// use robotics_lib::interface::Direction;
+/// use bob_lib::enhanced_map::BobMap;
+/// use bob_lib::navigation::estimate_step_cost;
+///
+/// let map: BobMap;
+/// if let Some((energy, rocks)) = estimate_step_cost(&map, (4, 2), Direction::Up) {
+///     println!("going up costs {} energy and {} rocks", energy, rocks);
+/// }
+/// ```
+pub fn estimate_step_cost(
+    map: &BobMap,
+    (x, y): (usize, usize),
+    direction: Direction,
+) -> Option<(isize, isize)> {
+    let to = match direction {
+        Direction::Up => (x.checked_sub(1)?, y),
+        Direction::Down => (x.checked_add(1)?, y),
+        Direction::Left => (x, y.checked_sub(1)?),
+        Direction::Right => (x, y.checked_add(1)?),
+    };
+    get_edge_cost(map.get_tile(to)?)
+}
+
+/// Enum that contains the reasons why [navigate_and_collect] can fail, one for every phase
+/// # Variants
+/// * `Path` - No path to the target was found, contains the error of the pathfinder
+/// * `Walk` - The robot couldn't walk the path, contains the error of [walk_path]
+/// * `Collect` - The robot reached the target but couldn't collect it, contains the error
+///   of [destroy_and_collect_item]
+#[derive(Debug)]
+pub enum NavigateError {
+    Path(PathError),
+    Walk(WalkError),
+    Collect(ActionError),
+}
+
+impl Display for NavigateError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            NavigateError::Path(error) => write!(f, "no path to the target: {}", error),
+            NavigateError::Walk(error) => write!(f, "walking to the target failed: {}", error),
+            NavigateError::Collect(error) => write!(f, "collecting the target failed: {}", error),
+        }
+    }
+}
+
+impl Error for NavigateError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            NavigateError::Path(error) => Some(error),
+            NavigateError::Walk(error) => Some(error),
+            NavigateError::Collect(error) => Some(error),
+        }
+    }
+}
+
+/// Function to go next to a tile and collect its content
+///
+/// The cheapest path to one of the tiles next to `target` is found with
+/// [BobMap::find_path], saving energy and using the robot energy and rocks and the current
+/// weather, the robot walks it with [walk_path] and then collects the content of `target`
+/// with [destroy_and_collect_item], which updates the goals
+///
+/// It returns [Err] containing a [NavigateError] telling which phase failed
+///
+/// It returns [Ok] containing the progress of the updated goal
+/// # Example
+/// ```
+/// use robotics_lib::runner::Robot;
+/// use robotics_lib::world::tile::Content;
+/// use robotics_lib::world::World;
+/// use bob_lib::enhanced_map::BobMap;
+/// use bob_lib::navigation::navigate_and_collect;
+/// use bob_lib::tracker::GoalTracker;
+///
+/// let mut robot: Robot;
+/// let mut world: World;
+/// let map: BobMap;
+/// let mut goal_tracker: GoalTracker;
+/// let (tree, wood) = ((4, 2), Some(Content::Tree(0)));
+/// let result = navigate_and_collect(&mut robot, &mut world, &map, tree, &mut goal_tracker, wood);
+/// ```
+pub fn navigate_and_collect(
+    robot: &mut impl Runnable,
+    world: &mut World,
+    map: &BobMap,
+    target: (usize, usize),
+    goal_tracker: &mut GoalTracker,
+    item_type: Option<Content>,
+) -> Result<GoalProgress, NavigateError> {
+    let start = (robot.get_coordinate().get_row(), robot.get_coordinate().get_col());
+    let mode = BobMode::EnergySave;
+    let backpack = robot.get_backpack();
+    let rocks = backpack.get_contents().get(&Content::Rock(0)).copied().unwrap_or(0) as isize;
+    let options = PlannerOptions::from_conditions(&look_at_sky(world))
+        .with_mode(mode)
+        .with_relation(mode.relation_from_robot(&*robot))
+        .with_max_energy(robot.get_energy().get_energy_level() as isize)
+        .with_rocks(rocks)
+        .with_rock_capacity(backpack.get_size() as isize);
+
+    // the target itself may not be walkable, so any tile next to it will do
+    let (x, y) = target;
+    let neighbours = [
+        (Some(x), y.checked_add(1)),
+        (Some(x), y.checked_sub(1)),
+        (x.checked_sub(1), Some(y)),
+        (x.checked_add(1), Some(y)),
+    ];
+    let mut best: Result<(Vec<(usize, usize)>, PathCost), PathError> =
+        Err(PathError::UnreachableGoal);
+    for (nx, ny) in neighbours {
+        let (Some(nx), Some(ny)) = (nx, ny) else {
+            continue;
+        };
+        let result = map.find_path(start, (nx, ny), &options);
+        best = match (best, result) {
+            (Ok(best), Ok(path)) if path.1 >= best.1 => Ok(best),
+            (Ok(best), Err(_)) => Ok(best),
+            (_, result) => result,
+        };
+    }
+    let (path, _) = best.map_err(NavigateError::Path)?;
+
+    walk_path(robot, world, &path).map_err(NavigateError::Walk)?;
+
+    let position = *path.last().unwrap_or(&start);
+    let direction = direction_between(position, target).ok_or(NavigateError::Walk(
+        WalkError::NotAdjacent {
+            from: position,
+            to: target,
+            energy_spent: 0,
+        },
+    ))?;
+    destroy_and_collect_item(robot, world, direction, goal_tracker, item_type)
+        .map_err(NavigateError::Collect)
+}
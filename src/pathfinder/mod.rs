@@ -0,0 +1,897 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+use std::fmt::{Display, Formatter};
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+
+use robotics_lib::runner::Runnable;
+use robotics_lib::world::environmental_conditions::{EnvironmentalConditions, WeatherType};
+use robotics_lib::world::tile::{Content, Tile, TileType};
+
+use crate::utils::{
+    chebyshev_distance, costs_relation, get_diagonal_edge_cost, get_edge_cost,
+    get_edge_cost_with_elevation, manhattan_distance, match_content_type_variant,
+};
+
+/// Cost as `(energy, rocks)` of jumping from an active teleport to another one
+pub const TELEPORT_COST: (isize, isize) = (30, 0);
+
+/// Rocks the backpack can hold when no other capacity is given, see [Node::with_capacity]
+pub const DEFAULT_ROCK_CAPACITY: isize = 20;
+
+/// Energy available to the pathfinder when no other budget is given, the maximum energy
+/// of a robot
+pub const DEFAULT_MAX_ENERGY: isize = 1000;
+
+/// First hour of the night, see [PlannerOptions::with_hour]
+pub const NIGHT_START: u8 = 20;
+
+/// First hour of the day, see [PlannerOptions::with_hour]
+pub const NIGHT_END: u8 = 6;
+
+/// Function to get how much the weather inflates the energy cost of moving
+///
+/// The multipliers are estimates of the extra energy the robot spends moving in bad
+/// weather, they are applied by [find_path] when [PlannerOptions] has a weather:
+/// * `Sunny` - 1.0
+/// * `Foggy` - 1.1
+/// * `Rainy` - 1.2
+/// * `TropicalMonsoon` - 1.5
+/// * `TrentinoSnow` - 1.5
+pub fn weather_energy_multiplier(weather: &WeatherType) -> f64 {
+    match weather {
+        WeatherType::Sunny => 1.0,
+        WeatherType::Foggy => 1.1,
+        WeatherType::Rainy => 1.2,
+        WeatherType::TropicalMonsoon => 1.5,
+        WeatherType::TrentinoSnow => 1.5,
+    }
+}
+
+/// Enum which says how the pathfinder weights the cost of every step
+///
+/// Every step has an energy cost and a material (rocks) cost, see `get_edge_cost`,
+/// the mode decides how the two are combined into a single cost, using `relation`
+/// as a divider
+/// # Variants
+/// * `EnergySave` - energy + rocks / relation, energy is the most important
+/// * `MaterialSave` - energy / relation + rocks, rocks are the most important
+/// * `AllOut` - energy + rocks, `relation` is ignored
+/// * `Weighted` - energy * energy_weight + rocks * material_weight, `relation` is ignored
+///
+/// The mode is only read by the pathfinder, so [Node::successors] takes it by reference
+/// and it can be copied freely
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BobMode {
+    EnergySave,
+    MaterialSave,
+    AllOut,
+    Weighted {
+        energy_weight: f64,
+        material_weight: f64,
+    },
+}
+
+impl BobMode {
+    /// Function to compute the `relation` used by the pathfinder from the robot state
+    ///
+    /// It reads the robot energy and the rocks in its backpack, then:
+    /// * `EnergySave` - returns the energy per rock, so the more energy the robot has
+    ///   compared to its rocks, the less the rocks count
+    /// * `MaterialSave` - returns the rocks per unit of energy, so the more rocks the robot
+    ///   has compared to its energy, the less the energy counts
+    /// * `AllOut` and `Weighted` - return 1, the relation is ignored anyway
+    ///
+    /// The result is never lower than 1
+    /// # Example
+    /// ```
+    /// use robotics_lib::runner::Robot;
+    /// use bob_lib::BobMode;
+    ///
+    /// let robot: Robot;
+    /// let relation = BobMode::EnergySave.relation_from_robot(&robot);
+    /// ```
+    pub fn relation_from_robot(&self, robot: &impl Runnable) -> f64 {
+        let energy = robot.get_energy().get_energy_level();
+        let rocks = robot
+            .get_backpack()
+            .get_contents()
+            .get(&Content::Rock(0))
+            .copied()
+            .unwrap_or(0);
+
+        match self {
+            BobMode::EnergySave => costs_relation(energy, rocks),
+            BobMode::MaterialSave => costs_relation(rocks, energy),
+            BobMode::AllOut | BobMode::Weighted { .. } => 1.0,
+        }
+    }
+}
+
+/// Options of the pathfinder, see [find_path]
+///
+/// They tell how the cost of every step is weighted, what the robot carries when leaving
+/// and the conditions the path will be walked in.
+/// The energy cost of every step is inflated by the [weather_energy_multiplier] of
+/// `weather` and, if `hour` is at night, by `night_multiplier`, so the planner prefers
+/// to travel in good conditions and its costs reflect the real energy spent
+///
+/// New options can be added without breaking the callers, build them from
+/// [Default] or [energy_save](PlannerOptions::energy_save) and the `with_` functions
+/// # Arguments
+/// * `mode` - How the energy and rocks cost of every step are combined, see [BobMode]
+/// * `relation` - The divider used by `mode`, see [BobMode::relation_from_robot]
+/// * `max_energy` - The energy available when leaving the start
+/// * `rocks` - The rocks in the backpack when leaving the start
+/// * `rock_capacity` - The rocks the backpack can hold
+/// * `weather` - The current weather, if known
+/// * `hour` - The current hour of the day, from 0 to 23, if known
+/// * `night_multiplier` - How much the energy cost is inflated between [NIGHT_START]
+///   and [NIGHT_END], 1 by default
+/// * `allow_diagonal` - Whether the robot can move diagonally too, false by default
+/// * `avoid_contents` - Contents the robot must never step on, even if the tiles could be
+///   crossed, matched by variant, e.g. `Content::Fire`
+/// * `prefer_contents` - Contents the robot would like to pass by, matched by variant, e.g.
+///   `Content::Coin(0)`
+/// * `prefer_bonus` - How much the cost of entering a tile with a preferred content is
+///   lowered, the cost never goes below 0
+/// # Example
+/// ```
+/// use robotics_lib::interface::look_at_sky;
+/// use robotics_lib::world::World;
+/// use bob_lib::pathfinder::PlannerOptions;
+///
+/// let world: World;
+/// let options = PlannerOptions::from_conditions(&look_at_sky(&world))
+///     .with_max_energy(300)
+///     .with_night_multiplier(2.0);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlannerOptions {
+    pub mode: BobMode,
+    pub relation: f64,
+    pub max_energy: isize,
+    pub rocks: isize,
+    pub rock_capacity: isize,
+    pub weather: Option<WeatherType>,
+    pub hour: Option<u8>,
+    pub night_multiplier: f64,
+    pub allow_diagonal: bool,
+    pub avoid_contents: Vec<Content>,
+    pub prefer_contents: Vec<Content>,
+    pub prefer_bonus: usize,
+}
+
+impl Default for PlannerOptions {
+    fn default() -> Self {
+        PlannerOptions {
+            mode: BobMode::EnergySave,
+            relation: 1.0,
+            max_energy: DEFAULT_MAX_ENERGY,
+            rocks: 0,
+            rock_capacity: DEFAULT_ROCK_CAPACITY,
+            weather: None,
+            hour: None,
+            night_multiplier: 1.0,
+            allow_diagonal: false,
+            avoid_contents: Vec::new(),
+            prefer_contents: Vec::new(),
+            prefer_bonus: 0,
+        }
+    }
+}
+
+impl PlannerOptions {
+    /// Function to create the options saving energy, the other options are the default ones
+    pub fn energy_save() -> PlannerOptions {
+        PlannerOptions::default().with_mode(BobMode::EnergySave)
+    }
+
+    /// Function to create the options from the environmental conditions of the world
+    ///
+    /// The weather is read with `get_weather_condition`, the hour is read from the `HH:MM`
+    /// string returned by `get_time_of_day_string` and left unknown if it can't be parsed
+    pub fn from_conditions(conditions: &EnvironmentalConditions) -> PlannerOptions {
+        let hour = conditions
+            .get_time_of_day_string()
+            .split(':')
+            .next()
+            .and_then(|hour| hour.trim().parse().ok());
+        PlannerOptions {
+            weather: Some(conditions.get_weather_condition()),
+            hour,
+            ..PlannerOptions::default()
+        }
+    }
+
+    /// Function to set how the energy and rocks cost of every step are combined
+    pub fn with_mode(mut self, mode: BobMode) -> PlannerOptions {
+        self.mode = mode;
+        self
+    }
+
+    /// Function to set the divider used by the mode
+    pub fn with_relation(mut self, relation: f64) -> PlannerOptions {
+        self.relation = relation;
+        self
+    }
+
+    /// Function to set the energy available when leaving the start
+    pub fn with_max_energy(mut self, max_energy: isize) -> PlannerOptions {
+        self.max_energy = max_energy;
+        self
+    }
+
+    /// Function to set the rocks in the backpack when leaving the start
+    pub fn with_rocks(mut self, rocks: isize) -> PlannerOptions {
+        self.rocks = rocks;
+        self
+    }
+
+    /// Function to set the rocks the backpack can hold
+    pub fn with_rock_capacity(mut self, rock_capacity: isize) -> PlannerOptions {
+        self.rock_capacity = rock_capacity;
+        self
+    }
+
+    /// Function to set the current weather
+    pub fn with_weather(mut self, weather: WeatherType) -> PlannerOptions {
+        self.weather = Some(weather);
+        self
+    }
+
+    /// Function to set the current hour of the day, from 0 to 23
+    ///
+    /// Hours from [NIGHT_START] included to [NIGHT_END] excluded are at night
+    pub fn with_hour(mut self, hour: u8) -> PlannerOptions {
+        self.hour = Some(hour);
+        self
+    }
+
+    /// Function to set how much the energy cost is inflated at night
+    pub fn with_night_multiplier(mut self, night_multiplier: f64) -> PlannerOptions {
+        self.night_multiplier = night_multiplier;
+        self
+    }
+
+    /// Function to set whether the robot can move diagonally too, see
+    /// [get_diagonal_edge_cost](crate::get_diagonal_edge_cost) for the cost of a
+    /// diagonal step
+    ///
+    /// Diagonal paths can't be walked by [walk_path](crate::navigation::walk_path), which
+    /// uses `go`
+    pub fn with_allow_diagonal(mut self, allow_diagonal: bool) -> PlannerOptions {
+        self.allow_diagonal = allow_diagonal;
+        self
+    }
+
+    /// Function to set the contents the robot must never step on, only their variant
+    /// is checked, so `Content::Rock(0)` avoids every rock
+    pub fn with_avoid_contents(mut self, avoid_contents: Vec<Content>) -> PlannerOptions {
+        self.avoid_contents = avoid_contents;
+        self
+    }
+
+    /// Function to set the contents the robot would like to pass by and how much the cost
+    /// of entering their tiles is lowered, only their variant is checked
+    ///
+    /// Only the cost used to choose the path is lowered, the [PathCost] of the path is
+    /// still the energy and rocks really spent
+    pub fn with_prefer_contents(
+        mut self,
+        prefer_contents: Vec<Content>,
+        prefer_bonus: usize,
+    ) -> PlannerOptions {
+        self.prefer_contents = prefer_contents;
+        self.prefer_bonus = prefer_bonus;
+        self
+    }
+
+    /// Function to check if the hour of the options is at night, an unknown hour is not
+    pub fn is_night(&self) -> bool {
+        self.hour.is_some_and(|hour| hour >= NIGHT_START || hour < NIGHT_END)
+    }
+
+    /// Function to get how much the energy cost of every step is inflated, combining the
+    /// weather and the time of day
+    pub fn energy_multiplier(&self) -> f64 {
+        let weather = self.weather.as_ref().map_or(1.0, weather_energy_multiplier);
+        let night = if self.is_night() { self.night_multiplier } else { 1.0 };
+        weather * night
+    }
+}
+
+/// Struct that contains the predicted cost of a path
+///
+/// Costs are compared by energy first and then by material
+/// # Fields
+/// * `energy` - Energy spent walking the path, inflated by the weather and the time of day
+/// * `material` - Rocks spent along the path minus the rocks gained, e.g. crossing mountains,
+///   negative if the robot ends the path with more rocks than it started with
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PathCost {
+    pub energy: usize,
+    pub material: isize,
+}
+
+/// Enum that contains the reasons why a path can't be found
+/// # Variants
+/// * `UnreachableGoal` - The goal is undiscovered or can't be entered (e.g. a wall)
+/// * `StartBlocked` - The start is undiscovered or every tile around it can't be entered,
+///   whatever the energy budget
+/// * `OutOfBounds` - The start or the goal are outside the map
+/// * `NoPath` - Both ends are fine but there is no path between them within the energy budget
+#[derive(Debug, Clone, PartialEq)]
+pub enum PathError {
+    UnreachableGoal,
+    StartBlocked,
+    OutOfBounds,
+    NoPath,
+}
+
+impl Display for PathError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PathError::UnreachableGoal => write!(f, "the goal can't be reached."),
+            PathError::StartBlocked => write!(f, "the start is blocked."),
+            PathError::OutOfBounds => write!(f, "the start or the goal are outside the map."),
+            PathError::NoPath => write!(f, "no path found within the energy budget."),
+        }
+    }
+}
+
+impl std::error::Error for PathError {}
+
+/// Node of the pathfinding graph
+///
+/// Two nodes are the same node if they have the same coordinates and `rocks`: the rocks
+/// decide which tiles can be crossed later, while the energy left only prunes the successors
+/// # Arguments
+/// * `x` - The row of the node
+/// * `y` - The column of the node
+/// * `energy` - The energy left after reaching the node
+/// * `rocks` - The rocks left after reaching the node, never more than `capacity`
+/// * `capacity` - The rocks the backpack can hold
+/// * `energy_multiplier` - How much the energy cost of every step is inflated, e.g. by
+///   the weather
+/// * `diagonal` - Whether the diagonal neighbours are successors too
+/// * `avoid_contents` - Contents of the tiles which are never successors, matched by variant
+/// * `prefer_contents` - Contents of the tiles whose cost is lowered by `prefer_bonus`
+#[derive(Debug, Clone)]
+pub struct Node {
+    pub x: usize,
+    pub y: usize,
+    pub energy: isize,
+    pub rocks: isize,
+    pub capacity: isize,
+    pub energy_multiplier: f64,
+    pub diagonal: bool,
+    pub avoid_contents: Arc<Vec<Content>>,
+    pub prefer_contents: Arc<Vec<Content>>,
+    pub prefer_bonus: usize,
+}
+
+impl PartialEq<Self> for Node {
+    fn eq(&self, other: &Self) -> bool {
+        self.x == other.x && self.y == other.y && self.rocks == other.rocks
+    }
+}
+
+impl Eq for Node {}
+
+impl Hash for Node {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.x.hash(state);
+        self.y.hash(state);
+        self.rocks.hash(state);
+    }
+}
+
+impl Node {
+    pub fn new(x: usize, y: usize, energy: isize, rocks: isize) -> Node {
+        Node {
+            x,
+            y,
+            energy,
+            rocks,
+            capacity: DEFAULT_ROCK_CAPACITY,
+            energy_multiplier: 1.0,
+            diagonal: false,
+            avoid_contents: Arc::default(),
+            prefer_contents: Arc::default(),
+            prefer_bonus: 0,
+        }
+    }
+
+    /// Function to set how many rocks the backpack can hold, the rocks of the node
+    /// are capped to the new capacity
+    pub fn with_capacity(mut self, capacity: isize) -> Node {
+        self.capacity = capacity.max(0);
+        self.rocks = self.rocks.min(self.capacity);
+        self
+    }
+
+    /// Function to set how much the energy cost of every step is inflated, multipliers
+    /// lower than 1 are ignored, see [PlannerOptions::energy_multiplier]
+    pub fn with_energy_multiplier(mut self, energy_multiplier: f64) -> Node {
+        self.energy_multiplier = energy_multiplier.max(1.0);
+        self
+    }
+
+    /// Function to let the node reach its diagonal neighbours too
+    pub fn with_diagonal(mut self, diagonal: bool) -> Node {
+        self.diagonal = diagonal;
+        self
+    }
+
+    /// Function to set the contents of the tiles the node must never reach, the list is
+    /// shared by every node reached from this one
+    pub fn with_avoid_contents(mut self, avoid_contents: Vec<Content>) -> Node {
+        self.avoid_contents = Arc::new(avoid_contents);
+        self
+    }
+
+    /// Function to set the contents of the tiles whose cost is lowered by `prefer_bonus`,
+    /// the list is shared by every node reached from this one
+    pub fn with_prefer_contents(
+        mut self,
+        prefer_contents: Vec<Content>,
+        prefer_bonus: usize,
+    ) -> Node {
+        self.prefer_contents = Arc::new(prefer_contents);
+        self.prefer_bonus = prefer_bonus;
+        self
+    }
+
+    /// Function to check if a tile contains one of the contents to avoid
+    fn avoids(&self, tile: &Tile) -> bool {
+        contains_variant(&self.avoid_contents, tile)
+    }
+
+    /// Function to lower the cost of entering a tile with a preferred content
+    fn discount(&self, tile: &Tile, weight: usize) -> usize {
+        if contains_variant(&self.prefer_contents, tile) {
+            weight.saturating_sub(self.prefer_bonus)
+        } else {
+            weight
+        }
+    }
+
+    /// Function to get every node reachable in one step from this node
+    ///
+    /// Climbing is taken into account, so flatter routes are preferred. If the node can move
+    /// diagonally the diagonal neighbours are successors too, see
+    /// [get_diagonal_edge_cost](crate::get_diagonal_edge_cost)
+    ///
+    /// It returns a Vec containing the reachable nodes and the cost to reach them,
+    /// undiscovered and impassable tiles are skipped, as well as tiles with a content to
+    /// avoid and tiles which would leave the node without energy or need more rocks than
+    /// the node has
+    pub fn successors(
+        &self,
+        map: &Vec<Vec<Option<Tile>>>,
+        mode: &BobMode,
+        relation: f64,
+    ) -> Vec<(Node, usize)> {
+        let mut ret = vec![];
+        let (x, y) = (self.x, self.y);
+        let current = map.get(x).and_then(|row| row.get(y)).and_then(|tile| tile.as_ref());
+
+        // right, left, up, down: directions leaving the map are skipped
+        let neighbours = [
+            (Some(x), y.checked_add(1)),
+            (Some(x), y.checked_sub(1)),
+            (x.checked_sub(1), Some(y)),
+            (x.checked_add(1), Some(y)),
+        ];
+
+        for (nx, ny) in neighbours {
+            let (Some(nx), Some(ny)) = (nx, ny) else {
+                continue;
+            };
+            if let Some(Some(tile)) = map.get(nx).and_then(|row| row.get(ny)) {
+                if self.avoids(tile) {
+                    continue;
+                }
+                let cost = match current {
+                    Some(current) => get_edge_cost_with_elevation(current, tile),
+                    None => get_edge_cost(tile),
+                };
+                if let Some(cost) = cost {
+                    let (node, weight) = self.step(nx, ny, cost, mode, relation);
+                    if node.energy >= 0 && node.rocks >= 0 {
+                        ret.push((node, self.discount(tile, weight)));
+                    }
+                }
+            }
+        }
+
+        if !self.diagonal {
+            return ret;
+        }
+
+        let diagonals = [
+            (x.checked_sub(1), y.checked_sub(1)),
+            (x.checked_sub(1), y.checked_add(1)),
+            (x.checked_add(1), y.checked_sub(1)),
+            (x.checked_add(1), y.checked_add(1)),
+        ];
+
+        for (nx, ny) in diagonals {
+            let (Some(nx), Some(ny)) = (nx, ny) else {
+                continue;
+            };
+            if let Some(Some(tile)) = map.get(nx).and_then(|row| row.get(ny)) {
+                if self.avoids(tile) {
+                    continue;
+                }
+                // an undiscovered current tile costs like the destination
+                let cost = get_diagonal_edge_cost(current.unwrap_or(tile), tile);
+                if let Some(cost) = cost {
+                    let (node, weight) = self.step(nx, ny, cost, mode, relation);
+                    if node.energy >= 0 && node.rocks >= 0 {
+                        ret.push((node, self.discount(tile, weight)));
+                    }
+                }
+            }
+        }
+
+        ret
+    }
+
+    /// Function to get every node reachable in one step from this node, teleports included
+    ///
+    /// Same as [successors](Node::successors), but if the node is on an active teleport
+    /// every other teleport in `teleports` is reachable paying [TELEPORT_COST]
+    pub fn successors_with_teleports(
+        &self,
+        map: &Vec<Vec<Option<Tile>>>,
+        mode: &BobMode,
+        relation: f64,
+        teleports: &[(usize, usize)],
+    ) -> Vec<(Node, usize)> {
+        let mut ret = self.successors(map, mode, relation);
+
+        if teleports.contains(&(self.x, self.y)) {
+            for &(x, y) in teleports.iter().filter(|&&coord| coord != (self.x, self.y)) {
+                if map[x][y].as_ref().is_some_and(|tile| self.avoids(tile)) {
+                    continue;
+                }
+                let (node, weight) = self.step(x, y, TELEPORT_COST, mode, relation);
+                if node.energy >= 0 && node.rocks >= 0 {
+                    ret.push((node, weight));
+                }
+            }
+        }
+
+        ret
+    }
+
+    /// Function to pay the cost of a step, the rocks gained are capped to the capacity while
+    /// the ones spent are not, so a step the node can't afford leaves negative rocks.
+    /// The energy cost is inflated by the energy multiplier and rounded up
+    fn step(
+        &self,
+        x: usize,
+        y: usize,
+        cost: (isize, isize),
+        mode: &BobMode,
+        relation: f64,
+    ) -> (Node, usize) {
+        let cost = ((cost.0 as f64 * self.energy_multiplier).ceil() as isize, cost.1);
+        let node = Node {
+            x,
+            y,
+            energy: self.energy - cost.0,
+            rocks: (self.rocks - cost.1).min(self.capacity),
+            capacity: self.capacity,
+            energy_multiplier: self.energy_multiplier,
+            diagonal: self.diagonal,
+            avoid_contents: Arc::clone(&self.avoid_contents),
+            prefer_contents: Arc::clone(&self.prefer_contents),
+            prefer_bonus: self.prefer_bonus,
+        };
+        (node, weight(cost, mode, relation))
+    }
+}
+
+/// Function to check if the content of a tile matches the variant of one of the contents
+fn contains_variant(contents: &[Content], tile: &Tile) -> bool {
+    contents.iter().any(|content| {
+        match_content_type_variant(Some(tile.content.clone()), Some(content.clone()))
+    })
+}
+
+/// Combines the energy and rocks cost of a step into a single cost, according to the mode
+fn weight((energy, rocks): (isize, isize), mode: &BobMode, relation: f64) -> usize {
+    let relation = if relation > 0.0 { relation } else { 1.0 };
+    let (energy, rocks) = (energy as f64, rocks as f64);
+    let weight = match mode {
+        BobMode::EnergySave => energy + rocks / relation,
+        BobMode::MaterialSave => energy / relation + rocks,
+        BobMode::AllOut => energy + rocks,
+        BobMode::Weighted {
+            energy_weight,
+            material_weight,
+        } => energy * energy_weight + rocks * material_weight,
+    };
+    weight.max(0.0).round() as usize
+}
+
+/// Function to get the coordinates of every active teleport of a map
+pub(crate) fn active_teleports(map: &Vec<Vec<Option<Tile>>>) -> Vec<(usize, usize)> {
+    let mut ret = vec![];
+    for (x, row) in map.iter().enumerate() {
+        for (y, tile) in row.iter().enumerate() {
+            if let Some(Tile { tile_type: TileType::Teleport(true), .. }) = tile {
+                ret.push((x, y));
+            }
+        }
+    }
+    ret
+}
+
+/// Function to find the cheapest path between two tiles of a map
+///
+/// Coordinates are `(row, col)`, the same ordering of
+/// [`Coordinate::get_row`](robotics_lib::world::coordinates::Coordinate::get_row) and
+/// [`Coordinate::get_col`](robotics_lib::world::coordinates::Coordinate::get_col), so
+/// `map[row][col]` is the tile at those coordinates
+///
+/// Active teleports are linked to each other, so the path can jump between them
+///
+/// The cost of every step is weighted according to `options.mode` and `options.relation`
+///
+/// `options.max_energy` is the energy available when leaving `start`, usually the robot's
+/// [`get_energy_level`](robotics_lib::energy::Energy::get_energy_level): paths which
+/// would need more energy than that are discarded
+///
+/// `options.rocks` are the rocks in the backpack when leaving `start` and
+/// `options.rock_capacity` how many rocks the backpack can hold: tiles which cost rocks
+/// (e.g. deep water) can only be crossed if the rocks collected so far are enough, tiles
+/// which give rocks (e.g. mountains) can't fill the backpack over its capacity
+///
+/// The energy cost of every step is inflated by the
+/// [energy_multiplier](PlannerOptions::energy_multiplier) of the weather and the time of
+/// day of `options`, so the path and its cost reflect the real energy spent
+///
+/// It returns [Err] containing a [PathError] if the goal can't be reached within `max_energy`
+///
+/// It returns [Ok] containing the coordinates of the path, start and goal included,
+/// and its [PathCost]: the energy it takes and the rocks it consumes
+/// # Example
+/// ```
+/// use robotics_lib::world::tile::Tile;
+/// use bob_lib::pathfinder::{find_path, PlannerOptions};
+///
+/// let map: Vec<Vec<Option<Tile>>>;
+/// let options = PlannerOptions::energy_save().with_max_energy(100);
+/// if let Ok((path, cost)) = find_path(&map, (0, 0), (3, 4), &options) {
+///     println!("{:?} costs {} energy and {} rocks", path, cost.energy, cost.material);
+/// }
+/// ```
+pub fn find_path(
+    map: &Vec<Vec<Option<Tile>>>,
+    start: (usize, usize),
+    goal: (usize, usize),
+    options: &PlannerOptions,
+) -> Result<(Vec<(usize, usize)>, PathCost), PathError> {
+    let (mode, relation) = (options.mode, options.relation);
+    let start_node = start_node(start, options);
+    let teleports = active_teleports(map);
+    check_ends(map, &start_node, goal, &mode, relation, &teleports)?;
+
+    let nodes = search(
+        &start_node,
+        changes_rocks(map),
+        |node| node.successors_with_teleports(map, &mode, relation, &teleports),
+        |_| 0,
+        |node| (node.x, node.y) == goal,
+    )
+    .ok_or(PathError::NoPath)?;
+    Ok(path_with_cost(nodes))
+}
+
+/// Function to find the cheapest path between two tiles of a map using A*
+///
+/// It takes the same arguments and returns the same result of [find_path], but
+/// it expands fewer nodes on big maps, guided by the Manhattan distance from the goal
+/// # Example
+/// ```
+/// use robotics_lib::world::environmental_conditions::WeatherType;
+/// use robotics_lib::world::tile::Tile;
+/// use bob_lib::pathfinder::{find_path_astar, PlannerOptions};
+///
+/// let map: Vec<Vec<Option<Tile>>>;
+/// let rainy = PlannerOptions::energy_save().with_weather(WeatherType::Rainy);
+/// if let Ok((path, cost)) = find_path_astar(&map, (0, 0), (3, 4), &rainy) {
+///     println!("{:?} costs {} energy and {} rocks", path, cost.energy, cost.material);
+/// }
+/// ```
+pub fn find_path_astar(
+    map: &Vec<Vec<Option<Tile>>>,
+    start: (usize, usize),
+    goal: (usize, usize),
+    options: &PlannerOptions,
+) -> Result<(Vec<(usize, usize)>, PathCost), PathError> {
+    let (mode, relation) = (options.mode, options.relation);
+    let start_node = start_node(start, options);
+    let teleports = active_teleports(map);
+    check_ends(map, &start_node, goal, &mode, relation, &teleports)?;
+
+    // cheapest possible step, so the heuristic never overestimates
+    let mut cheapest_step = map
+        .iter()
+        .flatten()
+        .filter_map(|tile| tile.as_ref().and_then(get_edge_cost))
+        .map(|cost| weight(cost, &mode, relation))
+        .min()
+        .unwrap_or(0);
+    if teleports.len() > 1 {
+        cheapest_step = cheapest_step.min(weight(TELEPORT_COST, &mode, relation));
+    }
+    if !options.prefer_contents.is_empty() {
+        cheapest_step = cheapest_step.saturating_sub(options.prefer_bonus);
+    }
+
+    let nodes = search(
+        &start_node,
+        changes_rocks(map),
+        |node| node.successors_with_teleports(map, &mode, relation, &teleports),
+        |node| {
+            let distance = if start_node.diagonal {
+                chebyshev_distance((node.x, node.y), goal)
+            } else {
+                manhattan_distance((node.x, node.y), goal)
+            };
+            if teleports.len() > 1 {
+                // a single jump can cover any distance
+                (distance * cheapest_step).min(weight(TELEPORT_COST, &mode, relation))
+            } else {
+                distance * cheapest_step
+            }
+        },
+        |node| (node.x, node.y) == goal,
+    )
+    .ok_or(PathError::NoPath)?;
+    Ok(path_with_cost(nodes))
+}
+
+/// Function to check if crossing some tile of a map costs or gives rocks, only then the
+/// rocks of a node can make the difference between two paths
+pub(crate) fn changes_rocks(map: &Vec<Vec<Option<Tile>>>) -> bool {
+    map.iter()
+        .flatten()
+        .filter_map(|tile| tile.as_ref().and_then(get_edge_cost))
+        .any(|(_, rocks)| rocks != 0)
+}
+
+/// Function to find the cheapest path from `start` to a node satisfying `success`, with A*
+/// guided by `heuristic`, which is Dijkstra when the heuristic is always 0
+///
+/// Nodes are told apart by their coordinates and, if `track_rocks` is set, by their rocks,
+/// never by their energy. A path reaching a node is dropped if another one reaching it is
+/// as cheap and leaves at least as much energy, so the paths kept for every node are the few
+/// trading cost for energy, however big the energy budget
+///
+/// It returns [None] if no node satisfying `success` can be reached
+///
+/// It returns [Some] containing the nodes of the path, `start` and the goal included
+pub(crate) fn search(
+    start: &Node,
+    track_rocks: bool,
+    mut successors: impl FnMut(&Node) -> Vec<(Node, usize)>,
+    mut heuristic: impl FnMut(&Node) -> usize,
+    mut success: impl FnMut(&Node) -> bool,
+) -> Option<Vec<Node>> {
+    let key = |node: &Node| (node.x, node.y, if track_rocks { node.rocks } else { 0 });
+    // every path found: its cost, its last node and the path it extends
+    let mut paths = vec![(0, start.clone(), None)];
+    // the paths not dropped for every node
+    let mut kept = HashMap::from([(key(start), vec![0])]);
+    let mut to_see = BinaryHeap::from([Reverse((heuristic(start), 0, Reverse(start.energy), 0))]);
+
+    while let Some(Reverse((_, cost, _, index))) = to_see.pop() {
+        // a path as cheap and leaving more energy was found after this one was queued
+        if !kept[&key(&paths[index].1)].contains(&index) {
+            continue;
+        }
+        let node = paths[index].1.clone();
+
+        if success(&node) {
+            let mut ret = vec![];
+            let mut current = Some(index);
+            while let Some(index) = current {
+                ret.push(paths[index].1.clone());
+                current = paths[index].2;
+            }
+            ret.reverse();
+            return Some(ret);
+        }
+
+        for (next, step) in successors(&node) {
+            let next_cost = cost + step;
+            let same_node = kept.entry(key(&next)).or_default();
+            let dominated = same_node.iter().any(|&other| {
+                paths[other].0 <= next_cost && paths[other].1.energy >= next.energy
+            });
+            if dominated {
+                continue;
+            }
+            same_node.retain(|&other| {
+                paths[other].0 < next_cost || paths[other].1.energy > next.energy
+            });
+            same_node.push(paths.len());
+
+            let estimate = next_cost + heuristic(&next);
+            to_see.push(Reverse((estimate, next_cost, Reverse(next.energy), paths.len())));
+            paths.push((next_cost, next, Some(index)));
+        }
+    }
+
+    None
+}
+
+/// Function to create the node the pathfinder starts from
+fn start_node((x, y): (usize, usize), options: &PlannerOptions) -> Node {
+    Node::new(x, y, options.max_energy, options.rocks)
+        .with_capacity(options.rock_capacity)
+        .with_energy_multiplier(options.energy_multiplier())
+        .with_diagonal(options.allow_diagonal)
+        .with_avoid_contents(options.avoid_contents.clone())
+        .with_prefer_contents(options.prefer_contents.clone(), options.prefer_bonus)
+}
+
+/// Function to turn the nodes of a path into its coordinates and cost, the cost is the
+/// difference between the energy and rocks of the first and the last node
+fn path_with_cost(nodes: Vec<Node>) -> (Vec<(usize, usize)>, PathCost) {
+    let cost = match (nodes.first(), nodes.last()) {
+        (Some(first), Some(last)) => PathCost {
+            energy: first.energy.saturating_sub(last.energy).max(0) as usize,
+            material: first.rocks - last.rocks,
+        },
+        _ => PathCost::default(),
+    };
+    (nodes.into_iter().map(|node| (node.x, node.y)).collect(), cost)
+}
+
+/// Function to check that a path between `start_node` and `goal` can exist, the start can
+/// be left through the active `teleports` too
+fn check_ends(
+    map: &Vec<Vec<Option<Tile>>>,
+    start_node: &Node,
+    goal: (usize, usize),
+    mode: &BobMode,
+    relation: f64,
+    teleports: &[(usize, usize)],
+) -> Result<(), PathError> {
+    let start = (start_node.x, start_node.y);
+    let tile_at = |(x, y): (usize, usize)| map.get(x).and_then(|row| row.get(y));
+
+    let (Some(start_tile), Some(goal_tile)) = (tile_at(start), tile_at(goal)) else {
+        return Err(PathError::OutOfBounds);
+    };
+    if goal_tile.as_ref().and_then(get_edge_cost).is_none() && start != goal {
+        return Err(PathError::UnreachableGoal);
+    }
+
+    let leaves = |node: &Node| {
+        start == goal
+            || !node.successors_with_teleports(map, mode, relation, teleports).is_empty()
+    };
+    // blocked whatever the budget: the energy and rocks are large enough for any step
+    let unbounded = Node {
+        energy: isize::MAX / 2,
+        rocks: isize::MAX / 2,
+        ..start_node.clone()
+    };
+    if start_tile.is_none() || !leaves(&unbounded) {
+        return Err(PathError::StartBlocked);
+    }
+    // the start could be left, but not within the budget
+    if !leaves(start_node) {
+        return Err(PathError::NoPath);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,182 @@
+use robotics_lib::world::tile::{Content, TileType};
+
+use crate::enhanced_map::BobMap;
+
+/// ANSI escape code which resets the colors
+const RESET: &str = "\x1b[0m";
+
+/// Function to render the map as text, one line for every row of the map
+///
+/// Every tile is rendered in a cell 3 characters wide, so the columns stay aligned:
+/// * the first character is the [tiletype_symbol], `?` if the tile is undiscovered
+/// * the second character is the [content_symbol], a space if there is no content
+/// * the third character is `*` if the tile has some pins, a space otherwise
+///
+/// The tile at `robot` is rendered as `@` in place of the tile type.
+/// If `use_color` is true every tile has the background color of its tile type and
+/// the robot is highlighted, using ANSI escape codes
+///
+/// It returns the rendered map, rows are separated by `\n`
+/// # Example
+/// ```
+/// use bob_lib::enhanced_map::BobMap;
+/// use bob_lib::render::render_bobmap;
+///
+/// let map: BobMap;
+/// println!("{}", render_bobmap(&map, Some((3, 4)), true));
+/// ```
+pub fn render_bobmap(map: &BobMap, robot: Option<(usize, usize)>, use_color: bool) -> String {
+    let mut rendered = String::new();
+
+    for (x, row) in map.get_map().iter().enumerate() {
+        for (y, (tile, pins, _)) in row.iter().enumerate() {
+            let is_robot = robot == Some((x, y));
+            let (tile_type, content) = match tile {
+                Some(tile) => (tiletype_symbol(&tile.tile_type), content_symbol(&tile.content)),
+                None => ('?', ' '),
+            };
+            let cell = format!(
+                "{}{}{}",
+                if is_robot { '@' } else { tile_type },
+                content,
+                if pins.is_empty() { ' ' } else { '*' }
+            );
+
+            if use_color {
+                let color = match (is_robot, tile) {
+                    (true, _) => "\x1b[1;7m",
+                    (false, Some(tile)) => tile_type_color(&tile.tile_type),
+                    (false, None) => "",
+                };
+                rendered.push_str(&format!("{}{}{}", color, cell, RESET));
+            } else {
+                rendered.push_str(&cell);
+            }
+        }
+        rendered.push('\n');
+    }
+
+    rendered
+}
+
+/// Function to get the symbol representing a tile type in a render
+///
+/// | Tile type      | Symbol |
+/// |----------------|--------|
+/// | `DeepWater`    | `~`    |
+/// | `ShallowWater` | `-`    |
+/// | `Sand`         | `.`    |
+/// | `Grass`        | `,`    |
+/// | `Street`       | `=`    |
+/// | `Hill`         | `n`    |
+/// | `Mountain`     | `^`    |
+/// | `Snow`         | `o`    |
+/// | `Lava`         | `!`    |
+/// | `Teleport`     | `&`    |
+/// | `Wall`         | `#`    |
+pub fn tiletype_symbol(tile_type: &TileType) -> char {
+    match tile_type {
+        TileType::DeepWater => '~',
+        TileType::ShallowWater => '-',
+        TileType::Sand => '.',
+        TileType::Grass => ',',
+        TileType::Street => '=',
+        TileType::Hill => 'n',
+        TileType::Mountain => '^',
+        TileType::Snow => 'o',
+        TileType::Lava => '!',
+        TileType::Teleport(_) => '&',
+        TileType::Wall => '#',
+    }
+}
+
+/// Function to get the symbol representing a content in a render
+///
+/// | Content      | Symbol |
+/// |--------------|--------|
+/// | `Rock`       | `R`    |
+/// | `Tree`       | `T`    |
+/// | `Garbage`    | `G`    |
+/// | `Fire`       | `F`    |
+/// | `Coin`       | `$`    |
+/// | `Bin`        | `U`    |
+/// | `Crate`      | `C`    |
+/// | `Bank`       | `B`    |
+/// | `Water`      | `W`    |
+/// | `Market`     | `M`    |
+/// | `Fish`       | `f`    |
+/// | `Building`   | `H`    |
+/// | `Bush`       | `b`    |
+/// | `JollyBlock` | `J`    |
+/// | `Scarecrow`  | `S`    |
+/// | `None`       | space  |
+pub fn content_symbol(content: &Content) -> char {
+    match content {
+        Content::Rock(_) => 'R',
+        Content::Tree(_) => 'T',
+        Content::Garbage(_) => 'G',
+        Content::Fire => 'F',
+        Content::Coin(_) => '$',
+        Content::Bin(_) => 'U',
+        Content::Crate(_) => 'C',
+        Content::Bank(_) => 'B',
+        Content::Water(_) => 'W',
+        Content::Market(_) => 'M',
+        Content::Fish(_) => 'f',
+        Content::Building => 'H',
+        Content::Bush(_) => 'b',
+        Content::JollyBlock(_) => 'J',
+        Content::Scarecrow => 'S',
+        Content::None => ' ',
+    }
+}
+
+/// Function to render the map with a single character for every tile
+///
+/// Denser than [render_bobmap], it's meant for big maps: every tile is rendered with the
+/// [content_symbol] of its content or, if it's empty, with the [tiletype_symbol] of its
+/// tile type. Undiscovered tiles are rendered as `?` and the tile at `robot` as `@`
+///
+/// It returns the rendered map, rows are separated by `\n`
+/// # Example
+/// ```
+/// use bob_lib::enhanced_map::BobMap;
+/// use bob_lib::render::render_dense;
+///
+/// let map: BobMap;
+/// println!("{}", render_dense(&map, Some((3, 4))));
+/// ```
+pub fn render_dense(map: &BobMap, robot: Option<(usize, usize)>) -> String {
+    let mut rendered = String::new();
+
+    for (x, row) in map.get_map().iter().enumerate() {
+        for (y, (tile, _, _)) in row.iter().enumerate() {
+            rendered.push(match tile {
+                _ if robot == Some((x, y)) => '@',
+                Some(tile) if tile.content != Content::None => content_symbol(&tile.content),
+                Some(tile) => tiletype_symbol(&tile.tile_type),
+                None => '?',
+            });
+        }
+        rendered.push('\n');
+    }
+
+    rendered
+}
+
+/// Function to get the ANSI escape code of the background color of a tile type
+fn tile_type_color(tile_type: &TileType) -> &'static str {
+    match tile_type {
+        TileType::DeepWater => "\x1b[44m",
+        TileType::ShallowWater => "\x1b[46m",
+        TileType::Sand => "\x1b[43m",
+        TileType::Grass => "\x1b[42m",
+        TileType::Street => "\x1b[100m",
+        TileType::Hill => "\x1b[102m",
+        TileType::Mountain => "\x1b[47m",
+        TileType::Snow => "\x1b[107m",
+        TileType::Lava => "\x1b[41m",
+        TileType::Teleport(_) => "\x1b[45m",
+        TileType::Wall => "\x1b[40m",
+    }
+}
@@ -0,0 +1,163 @@
+use robotics_lib::energy::Energy;
+use robotics_lib::event::events::Event;
+use robotics_lib::runner::backpack::BackPack;
+use robotics_lib::runner::{Robot, Runnable};
+use robotics_lib::world::coordinates::Coordinate;
+use robotics_lib::world::World;
+
+use crate::enhanced_map::BobMap;
+use crate::tracker::GoalTracker;
+
+/// The logic of a [BobRobot], invoked at every tick with the body, the map and the goal
+/// tracker of the robot apart, so they can be borrowed together, and the world
+pub type BobLogic = Box<dyn FnMut(&mut BobBody, &mut BobMap, &mut GoalTracker, &mut World)>;
+
+/// Struct that wraps the [Robot] moved by the logic of a [BobRobot]
+///
+/// It implements [Runnable], so it can be given to the functions of `robotics_lib::interface`
+/// and to the actions of [tracker](crate::tracker). The events of those actions reach the
+/// body and nothing else: the goals are moved by the actions of the tracker only, so a
+/// collect is never counted twice
+pub struct BobBody {
+    robot: Robot,
+}
+
+impl Runnable for BobBody {
+    fn process_tick(&mut self, _world: &mut World) {}
+
+    fn handle_event(&mut self, _event: Event) {}
+
+    fn get_energy(&self) -> &Energy {
+        &self.robot.energy
+    }
+
+    fn get_energy_mut(&mut self) -> &mut Energy {
+        &mut self.robot.energy
+    }
+
+    fn get_coordinate(&self) -> &Coordinate {
+        &self.robot.coordinate
+    }
+
+    fn get_coordinate_mut(&mut self) -> &mut Coordinate {
+        &mut self.robot.coordinate
+    }
+
+    fn get_backpack(&self) -> &BackPack {
+        &self.robot.backpack
+    }
+
+    fn get_backpack_mut(&mut self) -> &mut BackPack {
+        &mut self.robot.backpack
+    }
+}
+
+/// Struct that wraps a [Robot] together with a [BobMap] and a [GoalTracker], kept up to date
+/// without any boilerplate
+///
+/// It implements [Runnable], so it can be given to the `Runner` directly: at every tick the
+/// map is refreshed from the [World] and then the logic is invoked. The goals are moved by
+/// the actions of [tracker](crate::tracker) the logic runs on its [BobBody], see
+/// [destroy_and_collect_item](crate::tracker::destroy_and_collect_item)
+///
+/// The map is created at the first tick, before it is empty
+/// # Example
+/// ```
+/// use robotics_lib::interface::{go, Direction};
+/// use bob_lib::robot::BobRobot;
+/// use bob_lib::tracker::destroy_and_collect_item;
+///
+/// let robot = BobRobot::new(Box::new(|body, map, goal_tracker, world| {
+///     let _ = go(body, world, Direction::Up);
+///     let _ = destroy_and_collect_item(body, world, Direction::Up, goal_tracker, None);
+///     println!("{} tiles discovered", map.discovered_count());
+/// }));
+/// ```
+pub struct BobRobot {
+    body: BobBody,
+    map: BobMap,
+    goal_tracker: GoalTracker,
+    logic: BobLogic,
+}
+
+impl BobRobot {
+    /// Function to create a new robot running `logic` at every tick, with an empty goal
+    /// tracker
+    pub fn new(logic: BobLogic) -> BobRobot {
+        BobRobot::with_goal_tracker(logic, GoalTracker::new())
+    }
+
+    /// Function to create a new robot running `logic` at every tick and tracking the goals
+    /// of `goal_tracker`
+    pub fn with_goal_tracker(logic: BobLogic, goal_tracker: GoalTracker) -> BobRobot {
+        BobRobot {
+            body: BobBody {
+                robot: Robot::new(),
+            },
+            map: BobMap::from_tiles(vec![]),
+            goal_tracker,
+            logic,
+        }
+    }
+
+    /// Function to get the map of the robot
+    pub fn map(&self) -> &BobMap {
+        &self.map
+    }
+
+    /// Function to get the map of the robot to change it, e.g. to add pins or to pass it
+    /// to the view functions of [enhanced_map](crate::enhanced_map)
+    pub fn map_mut(&mut self) -> &mut BobMap {
+        &mut self.map
+    }
+
+    /// Function to get the goal tracker of the robot
+    pub fn goal_tracker(&self) -> &GoalTracker {
+        &self.goal_tracker
+    }
+
+    /// Function to get the goal tracker of the robot to change it, e.g. to add goals
+    pub fn goal_tracker_mut(&mut self) -> &mut GoalTracker {
+        &mut self.goal_tracker
+    }
+}
+
+impl Runnable for BobRobot {
+    fn process_tick(&mut self, world: &mut World) {
+        if self.map.dimensions() == (0, 0) {
+            self.map = BobMap::init(world);
+        } else {
+            self.map.refresh(world);
+        }
+
+        (self.logic)(&mut self.body, &mut self.map, &mut self.goal_tracker, world);
+    }
+
+    fn handle_event(&mut self, event: Event) {
+        self.body.handle_event(event);
+    }
+
+    fn get_energy(&self) -> &Energy {
+        &self.body.robot.energy
+    }
+
+    fn get_energy_mut(&mut self) -> &mut Energy {
+        &mut self.body.robot.energy
+    }
+
+    fn get_coordinate(&self) -> &Coordinate {
+        &self.body.robot.coordinate
+    }
+
+    fn get_coordinate_mut(&mut self) -> &mut Coordinate {
+        &mut self.body.robot.coordinate
+    }
+
+    fn get_backpack(&self) -> &BackPack {
+        &self.body.robot.backpack
+    }
+
+    fn get_backpack_mut(&mut self) -> &mut BackPack {
+        &mut self.body.robot.backpack
+    }
+}
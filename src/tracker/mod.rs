@@ -1,12 +1,17 @@
+use std::error::Error;
 use std::fmt;
 use std::fmt::Display;
 
-use crate::utils::{get_tile_in_direction, match_content_type_variant};
+use crate::enhanced_map::BobMap;
+use crate::utils::{find_direction_to, get_tile_in_direction, match_content_type_variant};
+use robotics_lib::event::events::Event;
 use robotics_lib::interface::{destroy, put, Direction};
 use robotics_lib::runner::Runnable;
 use robotics_lib::utils::LibError;
-use robotics_lib::world::tile::Content;
+use robotics_lib::world::tile::{Content, Tile};
 use robotics_lib::world::World;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 
 /// Represents various types of goals in a robotics context.
 ///
@@ -16,14 +21,111 @@ use robotics_lib::world::World;
 /// * `GetItems` - Represents a goal to retrieve items.
 /// * `SellItems` - Represents a goal to sell items.
 /// * `ThrowGarbage` - Represents a goal to dispose of garbage.
+/// * `Composite` - Represents a goal completed when all the goals with the given names are.
+/// * `Explore` - Represents a goal to discover new tiles.
+/// * `PutItems` - Represents a goal to place items on the map, e.g. rocks to build a street.
 #[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum GoalType {
     PutOutFire,
     GetItems,
     SellItems,
     ThrowGarbage,
+    Composite(Vec<String>),
+    Explore,
+    PutItems,
 }
 
+/// Represents the progress made by a goal after an update.
+///
+/// # Arguments
+///
+/// * `goal_name` - The name of the updated goal.
+/// * `delta` - The number of items the update removed from the items left, it's lower than the
+///   removed quantity if more items than needed were removed.
+/// * `items_left` - The number of items left to complete the goal after the update.
+/// * `just_completed` - Indicates whether the update completed the goal.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GoalProgress {
+    pub goal_name: String,
+    pub delta: u32,
+    pub items_left: u32,
+    pub just_completed: bool,
+}
+
+/// Represents the errors of the goal tracker.
+///
+/// # Variants
+///
+/// * `NotFound` - No goal matches the given goal type and item type.
+/// * `MissingField` - A [GoalBuilder] was built without a required field, contains its name.
+#[derive(Debug, PartialEq)]
+pub enum GoalError {
+    NotFound {
+        goal_type: GoalType,
+        item_type: Option<Content>,
+    },
+    MissingField(&'static str),
+}
+
+/// Represents the errors of the actions updating the goal tracker.
+///
+/// # Variants
+///
+/// * `Lib` - The action failed, contains the error returned by robotics_lib.
+/// * `Goal` - The action succeeded but no goal could be updated, contains the error
+///   of the tracker and the quantity removed by the action.
+#[derive(Debug)]
+pub enum ActionError {
+    Lib(LibError),
+    Goal {
+        error: GoalError,
+        removed_quantity: usize,
+    },
+}
+
+impl Display for GoalError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GoalError::NotFound { goal_type, item_type } => {
+                write!(f, "no goal of type {:?} for {:?}.", goal_type, item_type)
+            }
+            GoalError::MissingField(field) => write!(f, "the goal has no {}.", field),
+        }
+    }
+}
+
+impl Error for GoalError {}
+
+impl Display for ActionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ActionError::Lib(error) => write!(f, "the action failed: {:?}.", error),
+            ActionError::Goal { error, removed_quantity } => {
+                write!(f, "{} items removed but {}", removed_quantity, error)
+            }
+        }
+    }
+}
+
+impl Error for ActionError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ActionError::Lib(_) => None,
+            ActionError::Goal { error, .. } => Some(error),
+        }
+    }
+}
+
+impl From<LibError> for ActionError {
+    fn from(error: LibError) -> Self {
+        ActionError::Lib(error)
+    }
+}
+
+/// The priority given to goals created without one.
+pub const DEFAULT_PRIORITY: u8 = 128;
+
 /// Represents a goal in a robotics context.
 ///
 /// # Arguments
@@ -35,7 +137,14 @@ pub enum GoalType {
 /// * `completed` - Indicates whether the goal has been completed (true) or not (false).
 /// * `goal_quantity` - The quantity required to fulfill the goal.
 /// * `items_left` - The number of items left to complete the goal.
-#[derive(Debug)]
+/// * `deadline` - The optional number of ticks within which the goal must be completed.
+/// * `ticks_left` - The number of ticks left before the deadline, if any.
+/// * `expired` - Indicates whether the deadline passed before the goal was completed.
+/// * `priority` - The importance of the goal, higher values are more important.
+/// * `match_value` - Indicates whether `item_type` must match exactly (e.g. `Rock(3)` only)
+///   instead of just its variant (e.g. any `Rock`).
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Goal {
     /// The name of the goal.
     pub name: String,
@@ -57,11 +166,28 @@ pub struct Goal {
 
     /// The number of items left to complete the goal.
     pub items_left: u32,
+
+    /// The optional number of ticks within which the goal must be completed.
+    pub deadline: Option<u32>,
+
+    /// The number of ticks left before the deadline, if any.
+    pub ticks_left: Option<u32>,
+
+    /// Indicates whether the deadline passed before the goal was completed.
+    pub expired: bool,
+
+    /// The importance of the goal, higher values are more important.
+    pub priority: u8,
+
+    /// Indicates whether `item_type` must match exactly instead of just its variant.
+    pub match_value: bool,
 }
 
 impl Goal {
     /// Creates a new Goal instance.
     ///
+    /// A goal with a quantity of 0 has nothing left to do, so it's created already completed.
+    ///
     /// # Arguments
     /// * `name` - The name of the goal.
     /// * `description` - The description of the goal.
@@ -83,22 +209,114 @@ impl Goal {
             description,
             goal_type,
             item_type,
-            completed: false,
+            completed: goal_quantity == 0,
             goal_quantity,
             items_left: goal_quantity,
+            deadline: None,
+            ticks_left: None,
+            expired: false,
+            priority: DEFAULT_PRIORITY,
+            match_value: false,
         }
     }
 
+    /// Creates a [GoalBuilder], to set the fields of the goal by name.
+    ///
+    /// # Returns
+    /// A new `GoalBuilder` with the default values.
+    pub fn builder() -> GoalBuilder {
+        GoalBuilder::default()
+    }
+
+    /// Sets the priority of the goal.
+    ///
+    /// # Arguments
+    /// * `priority` - The importance of the goal, higher values are more important.
+    ///
+    /// # Returns
+    /// The same `Goal` with the priority set.
+    pub fn with_priority(mut self, priority: u8) -> Goal {
+        self.priority = priority;
+        self
+    }
+
+    /// Sets whether the goal must match the exact content value.
+    ///
+    /// # Arguments
+    /// * `match_value` - If true only actions on exactly `item_type` (e.g. `Rock(3)`) update the
+    ///   goal, otherwise any content of the same variant (e.g. any `Rock`) does.
+    ///
+    /// # Returns
+    /// The same `Goal` with the flag set.
+    pub fn with_match_value(mut self, match_value: bool) -> Goal {
+        self.match_value = match_value;
+        self
+    }
+
+    /// Resets the goal, so it can be completed again.
+    ///
+    /// The items left go back to the goal quantity and the goal is neither completed
+    /// nor expired anymore, unless its quantity is 0, if it has a deadline its ticks
+    /// start over.
+    pub fn reset(&mut self) {
+        self.items_left = self.goal_quantity;
+        self.completed = self.goal_quantity == 0;
+        self.expired = false;
+        self.ticks_left = self.deadline;
+    }
+
+    /// Returns true if the goal is updated by the content `item_type`.
+    /// Exploration goals have no content, so they match any `item_type`.
+    fn matches_item(&self, item_type: &Option<Content>) -> bool {
+        if self.goal_type == GoalType::Explore {
+            true
+        } else if self.match_value {
+            self.item_type.is_some() && self.item_type == *item_type
+        } else {
+            match_content_type_variant(self.item_type.clone(), item_type.clone())
+        }
+    }
+
+    /// Creates a new composite Goal instance.
+    ///
+    /// A composite goal is completed when all its children are, the number of children
+    /// still to be completed is kept in `items_left`.
+    ///
+    /// # Arguments
+    /// * `name` - The name of the goal.
+    /// * `description` - The description of the goal.
+    /// * `children` - The names of the goals which must be completed.
+    ///
+    /// # Returns
+    /// A new `Goal` instance of type `GoalType::Composite`.
+    pub fn composite(name: String, description: String, children: Vec<String>) -> Goal {
+        let goal_quantity = children.len() as u32;
+        Goal::new(name, description, GoalType::Composite(children), None, goal_quantity)
+    }
+
+    /// Sets a deadline to the goal.
+    ///
+    /// # Arguments
+    /// * `deadline` - The number of ticks within which the goal must be completed,
+    ///   see [GoalTracker::tick].
+    ///
+    /// # Returns
+    /// The same `Goal` with the deadline set.
+    pub fn with_deadline(mut self, deadline: u32) -> Goal {
+        self.deadline = Some(deadline);
+        self.ticks_left = Some(deadline);
+        self
+    }
+
     /// Updates the goal progress based on the removed quantity.
-    fn update_progress(&mut self, removed_quantity: usize) {
+    /// Returns true only if this update completed the goal.
+    fn update_progress(&mut self, removed_quantity: usize) -> bool {
+        let was_completed = self.completed;
         self.items_left = self.items_left.saturating_sub(removed_quantity as u32);
         if self.items_left == 0 {
             self.completed = true; // Imposta il goal come completato se non ci sono più elementi rimasti.
         }
-    }
-
-    fn is_completed(&self) -> bool {
-        self.items_left == 0
+        !was_completed && self.completed
     }
 
     pub fn get_name(&self) -> &String {
@@ -124,6 +342,152 @@ impl Goal {
     pub fn get_items_left(&self) -> &u32 {
         &self.items_left
     }
+
+    pub fn get_deadline(&self) -> &Option<u32> {
+        &self.deadline
+    }
+
+    pub fn get_ticks_left(&self) -> &Option<u32> {
+        &self.ticks_left
+    }
+
+    pub fn get_priority(&self) -> &u8 {
+        &self.priority
+    }
+
+    /// Returns true if the deadline passed before the goal was completed.
+    pub fn is_expired(&self) -> bool {
+        self.expired
+    }
+
+    /// Returns the progress of the goal.
+    ///
+    /// # Returns
+    /// f32 - The fraction of the goal quantity already done, between 0.0 and 1.0.
+    /// A goal with a quantity of 0 is considered complete, so its progress is 1.0.
+    pub fn progress(&self) -> f32 {
+        if self.goal_quantity == 0 {
+            return 1.0;
+        }
+        let done = self.goal_quantity.saturating_sub(self.items_left);
+        (done as f32 / self.goal_quantity as f32).clamp(0.0, 1.0)
+    }
+}
+
+/// Builds a [Goal] one field at a time, see [Goal::builder].
+///
+/// The name and the goal type are required, the other fields are optional: the description
+/// is empty, there is no item type, the quantity is 1, the priority is [DEFAULT_PRIORITY]
+/// and there is no deadline.
+///
+/// # Examples
+/// ```
+/// use robotics_lib::world::tile::Content;
+/// use bob_lib::tracker::{Goal, GoalType};
+///
+/// let goal = Goal::builder()
+///     .name("rocks")
+///     .description("collect some rocks")
+///     .goal_type(GoalType::GetItems)
+///     .item(Content::Rock(0))
+///     .quantity(5)
+///     .priority(200)
+///     .deadline(50)
+///     .build()
+///     .unwrap();
+/// ```
+#[derive(Debug)]
+pub struct GoalBuilder {
+    name: Option<String>,
+    description: String,
+    goal_type: Option<GoalType>,
+    item_type: Option<Content>,
+    quantity: u32,
+    priority: u8,
+    deadline: Option<u32>,
+    match_value: bool,
+}
+
+impl Default for GoalBuilder {
+    fn default() -> Self {
+        GoalBuilder {
+            name: None,
+            description: String::new(),
+            goal_type: None,
+            item_type: None,
+            quantity: 1,
+            priority: DEFAULT_PRIORITY,
+            deadline: None,
+            match_value: false,
+        }
+    }
+}
+
+impl GoalBuilder {
+    /// Sets the name of the goal.
+    pub fn name(mut self, name: impl Into<String>) -> GoalBuilder {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Sets the description of the goal.
+    pub fn description(mut self, description: impl Into<String>) -> GoalBuilder {
+        self.description = description.into();
+        self
+    }
+
+    /// Sets the type of the goal.
+    pub fn goal_type(mut self, goal_type: GoalType) -> GoalBuilder {
+        self.goal_type = Some(goal_type);
+        self
+    }
+
+    /// Sets the content associated with the goal.
+    pub fn item(mut self, item_type: Content) -> GoalBuilder {
+        self.item_type = Some(item_type);
+        self
+    }
+
+    /// Sets the quantity required to fulfill the goal.
+    pub fn quantity(mut self, quantity: u32) -> GoalBuilder {
+        self.quantity = quantity;
+        self
+    }
+
+    /// Sets the priority of the goal, see [Goal::with_priority].
+    pub fn priority(mut self, priority: u8) -> GoalBuilder {
+        self.priority = priority;
+        self
+    }
+
+    /// Sets the deadline of the goal, see [Goal::with_deadline].
+    pub fn deadline(mut self, deadline: u32) -> GoalBuilder {
+        self.deadline = Some(deadline);
+        self
+    }
+
+    /// Sets whether the goal must match the exact content value, see [Goal::with_match_value].
+    pub fn match_value(mut self, match_value: bool) -> GoalBuilder {
+        self.match_value = match_value;
+        self
+    }
+
+    /// Builds the goal.
+    ///
+    /// # Returns
+    /// Result<Goal, GoalError> - Err(GoalError::MissingField) if the name or the goal type
+    /// were not set, Ok(Goal) otherwise.
+    pub fn build(self) -> Result<Goal, GoalError> {
+        let name = self.name.ok_or(GoalError::MissingField("name"))?;
+        let goal_type = self.goal_type.ok_or(GoalError::MissingField("goal type"))?;
+        let goal = Goal::new(name, self.description, goal_type, self.item_type, self.quantity)
+            .with_priority(self.priority)
+            .with_match_value(self.match_value);
+        Ok(match self.deadline {
+            Some(deadline) => goal.with_deadline(deadline),
+            None => goal,
+        })
+    }
 }
 
 impl Display for Goal {
@@ -148,12 +512,40 @@ impl Display for Goal {
 ///
 /// * `goals` - A vector storing the list of goals to be tracked.
 /// * `completed_number` - The count of completed goals within the tracker.
+/// * `on_complete` - The callbacks invoked when a goal is completed.
+///
+/// With the `serde` feature the tracker can be serialized, e.g. to JSON with
+/// [to_json](GoalTracker::to_json), to resume a mission later: the callbacks are not
+/// serialized and must be registered again.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct GoalTracker {
     /// The list of goals being tracked.
     goals: Vec<Goal>,
 
     /// The number of completed goals.
     completed_number: usize,
+
+    /// The callbacks invoked when a goal is completed, in registration order.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    on_complete: Vec<Box<dyn FnMut(&Goal)>>,
+}
+
+/// Two trackers are equal if they have the same goals, in the same order, and the same number
+/// of completed goals, the callbacks are not compared.
+impl PartialEq for GoalTracker {
+    fn eq(&self, other: &Self) -> bool {
+        self.goals == other.goals && self.completed_number == other.completed_number
+    }
+}
+
+impl fmt::Debug for GoalTracker {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("GoalTracker")
+            .field("goals", &self.goals)
+            .field("completed_number", &self.completed_number)
+            .field("on_complete", &self.on_complete.len())
+            .finish()
+    }
 }
 
 impl GoalTracker {
@@ -161,9 +553,14 @@ impl GoalTracker {
         GoalTracker {
             goals: Vec::new(),
             completed_number: 0,
+            on_complete: Vec::new(),
         }
     }
+    /// Adds a goal to the tracker, a goal already completed is counted as completed.
     pub fn add_goal(&mut self, goal: Goal) {
+        if goal.completed {
+            self.completed_number += 1;
+        }
         self.goals.push(goal);
     }
 
@@ -186,15 +583,164 @@ impl GoalTracker {
         }
     }
 
-    /// Finds a goal an returs a mut ref to such goal. Finds the first occurrency.
-    fn find_goal_mut(
-        &mut self,
-        goal_type: GoalType,
-        item_type: Option<Content>,
-    ) -> Option<&mut Goal> {
-        self.goals.iter_mut().find(|goal| {
-            goal.goal_type == goal_type
-                && match_content_type_variant(goal.item_type.clone(), item_type.clone())
+    /// Merges the goals of another tracker into this one.
+    ///
+    /// The goals of `other` are appended, a goal with the same name of a goal already
+    /// tracked replaces it only if it's more progressed, see [Goal::progress]. The callbacks
+    /// of `other` are kept too, after the ones of this tracker.
+    ///
+    /// # Arguments
+    /// * `other` - The tracker whose goals are merged.
+    ///
+    /// # Returns
+    /// Vec<String> - The names of the goals found in both trackers.
+    ///
+    /// # Examples
+    /// ```
+    /// use bob_lib::tracker::GoalTracker;
+    ///
+    /// let mut goal_tracker = GoalTracker::new();
+    /// let conflicts = goal_tracker.merge(GoalTracker::new());
+    /// assert!(conflicts.is_empty());
+    /// ```
+    pub fn merge(&mut self, other: GoalTracker) -> Vec<String> {
+        let mut conflicts = vec![];
+        for goal in other.goals {
+            match self.goals.iter().position(|tracked| tracked.name == goal.name) {
+                Some(index) => {
+                    conflicts.push(goal.name.clone());
+                    if goal.progress() > self.goals[index].progress() {
+                        self.goals[index] = goal;
+                    }
+                }
+                None => self.goals.push(goal),
+            }
+        }
+        self.on_complete.extend(other.on_complete);
+        self.completed_number = self.goals.iter().filter(|goal| goal.completed).count();
+        conflicts
+    }
+
+    /// Serializes the tracker to JSON (requires the `serde` feature).
+    ///
+    /// # Returns
+    /// Result<String, serde_json::Error> - The JSON representation of the tracker.
+    #[cfg(feature = "serde")]
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string(self)
+    }
+
+    /// Restores a tracker serialized with `to_json` (requires the `serde` feature).
+    ///
+    /// # Arguments
+    /// * `json` - The JSON representation of the tracker.
+    ///
+    /// # Returns
+    /// Result<GoalTracker, serde_json::Error> - The restored tracker, without callbacks.
+    #[cfg(feature = "serde")]
+    pub fn from_json(json: &str) -> Result<GoalTracker, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+
+    /// Finds a goal based on its name.
+    ///
+    /// # Arguments
+    /// * `name` - The name of the goal.
+    ///
+    /// # Returns
+    /// Option<&Goal> - The first goal with such name if found, None otherwise.
+    pub fn get_goal(&self, name: &str) -> Option<&Goal> {
+        self.goals.iter().find(|goal| goal.name == name)
+    }
+
+    /// Finds a goal based on its name and returns a mutable reference to it.
+    ///
+    /// # Arguments
+    /// * `name` - The name of the goal.
+    ///
+    /// # Returns
+    /// Option<&mut Goal> - The first goal with such name if found, None otherwise.
+    pub fn get_goal_mut(&mut self, name: &str) -> Option<&mut Goal> {
+        self.goals.iter_mut().find(|goal| goal.name == name)
+    }
+
+    /// Finds a goal an returs its index. Finds the first occurrency.
+    fn find_goal_index(&self, goal_type: &GoalType, item_type: &Option<Content>) -> Option<usize> {
+        self.goals.iter().position(|goal| {
+            !goal.expired && goal.goal_type == *goal_type && goal.matches_item(item_type)
+        })
+    }
+
+    /// Registers a callback invoked every time a goal is completed.
+    ///
+    /// The callbacks are invoked from `update_manual` and from the actions updating the
+    /// goals, in the order they were registered, with the goal which has just been completed.
+    ///
+    /// # Arguments
+    /// * `f` - The callback to be invoked.
+    ///
+    /// # Examples
+    /// ```
+    /// use bob_lib::tracker::GoalTracker;
+    ///
+    /// let mut goal_tracker = GoalTracker::new();
+    /// goal_tracker.on_complete(Box::new(|goal| println!("Completed: {}", goal.get_name())));
+    /// ```
+    pub fn on_complete(&mut self, f: Box<dyn FnMut(&Goal)>) {
+        self.on_complete.push(f);
+    }
+
+    /// Invokes every callback registered with `on_complete` on the goal at `index`,
+    /// then updates the composite goals containing it.
+    fn notify_completed(&mut self, index: usize) {
+        let goal = &self.goals[index];
+        for callback in self.on_complete.iter_mut() {
+            callback(goal);
+        }
+        self.update_composites(self.goals[index].name.clone());
+    }
+
+    /// Updates the composite goals which have `child` among their children, completing
+    /// the ones whose children are all completed.
+    fn update_composites(&mut self, child: String) {
+        let parents: Vec<usize> = (0..self.goals.len())
+            .filter(|&index| match &self.goals[index].goal_type {
+                GoalType::Composite(children) => {
+                    !self.goals[index].completed && children.contains(&child)
+                }
+                _ => false,
+            })
+            .collect();
+
+        for index in parents {
+            let items_left = match &self.goals[index].goal_type {
+                GoalType::Composite(children) => children
+                    .iter()
+                    .filter(|name| {
+                        !self.goals.iter().any(|goal| goal.name == **name && goal.completed)
+                    })
+                    .count(),
+                _ => continue,
+            };
+            self.goals[index].items_left = items_left as u32;
+            if items_left == 0 {
+                self.goals[index].completed = true;
+                self.completed_number += 1;
+                self.notify_completed(index);
+            }
+        }
+    }
+
+    /// Returns true if the composite goal with the given name is completed.
+    ///
+    /// # Arguments
+    /// * `name` - The name of the composite goal.
+    ///
+    /// # Returns
+    /// bool - false if the goal is not completed, is not found or is not a composite goal.
+    pub fn is_composite_complete(&self, name: &str) -> bool {
+        self.goals.iter().any(|goal| {
+            goal.name == name && matches!(goal.goal_type, GoalType::Composite(_)) && goal.completed
         })
     }
 
@@ -211,38 +757,207 @@ impl GoalTracker {
         self.completed_number
     }
 
-    /// Update the goal tracker based on the action result and the corresponding goal type.
+    /// Returns the number of tracked goals, completed and expired ones included.
+    pub fn len(&self) -> usize {
+        self.goals.len()
+    }
+
+    /// Returns true if the tracker has no goals.
+    pub fn is_empty(&self) -> bool {
+        self.goals.is_empty()
+    }
+
+    /// Removes every goal from the tracker, e.g. to start a new mission phase.
+    ///
+    /// The completed goals are not counted anymore, the callbacks are kept.
+    pub fn clear(&mut self) {
+        self.goals.clear();
+        self.completed_number = 0;
+    }
+
+    /// Advances the time of the tracker by one tick.
+    ///
+    /// Every goal with a deadline which is neither completed nor expired loses one tick,
+    /// goals which run out of ticks are marked as expired: they are kept in the tracker,
+    /// but they are no longer updated by the actions or by `update_manual`.
+    pub fn tick(&mut self) {
+        for goal in self.goals.iter_mut().filter(|goal| !goal.completed && !goal.expired) {
+            if let Some(ticks_left) = goal.ticks_left.as_mut() {
+                *ticks_left = ticks_left.saturating_sub(1);
+                if *ticks_left == 0 {
+                    goal.expired = true;
+                }
+            }
+        }
+    }
+
+    /// Resets every goal of the tracker, see [Goal::reset].
+    ///
+    /// The goals are kept, so a repeatable mission can start over without adding them again.
+    pub fn reset_all(&mut self) {
+        self.goals.iter_mut().for_each(Goal::reset);
+        self.completed_number = self.goals.iter().filter(|goal| goal.completed).count();
+    }
+
+    /// Returns the goals which still have to be completed, most important first.
+    ///
+    /// Completed and expired goals are left out, goals with the same priority are
+    /// kept in the order they were added.
+    pub fn goals_by_priority(&self) -> Vec<&Goal> {
+        let mut goals: Vec<&Goal> = self
+            .goals
+            .iter()
+            .filter(|goal| !goal.completed && !goal.expired)
+            .collect();
+        // sort_by_key is stable, so ties keep the insertion order
+        goals.sort_by_key(|goal| std::cmp::Reverse(goal.priority));
+        goals
+    }
+
+    /// Returns the goals whose deadline passed before they were completed.
+    pub fn get_expired(&self) -> Vec<&Goal> {
+        self.goals.iter().filter(|goal| goal.expired).collect()
+    }
+
+    /// Returns true if every tracked goal is completed.
+    ///
+    /// A tracker without goals has no mission, so it's not considered completed.
+    pub fn all_completed(&self) -> bool {
+        !self.goals.is_empty() && self.goals.iter().all(|goal| goal.completed)
+    }
+
+    /// Returns the goals which are not completed yet, in the order they were added.
+    ///
+    /// Expired goals are included, see [get_expired](GoalTracker::get_expired).
+    pub fn incomplete_goals(&self) -> Vec<&Goal> {
+        self.goals.iter().filter(|goal| !goal.completed).collect()
+    }
+
+    /// Returns the value still to be gained from the goals, e.g. to estimate the score.
+    ///
+    /// For every goal which is neither completed nor expired the items left are multiplied by
+    /// the value of its item type, goals without an item type (e.g. exploration and
+    /// composite goals) are worth nothing.
+    ///
+    /// # Arguments
+    /// * `value_fn` - The value of one item of a content.
+    ///
+    /// # Returns
+    /// f64 - The sum of the value left in every goal.
+    pub fn estimated_value(&self, value_fn: impl Fn(&Content) -> f64) -> f64 {
+        self.goals
+            .iter()
+            .filter(|goal| !goal.completed && !goal.expired)
+            .filter_map(|goal| {
+                let item_type = goal.item_type.as_ref()?;
+                Some(goal.items_left as f64 * value_fn(item_type))
+            })
+            .sum()
+    }
+
+    /// Returns the average progress of all the tracked goals.
+    ///
+    /// # Returns
+    /// f32 - The average of the progress of every goal, between 0.0 and 1.0.
+    /// A tracker without goals has nothing left to do, so its progress is 1.0.
+    pub fn overall_progress(&self) -> f32 {
+        if self.goals.is_empty() {
+            return 1.0;
+        }
+        self.goals.iter().map(Goal::progress).sum::<f32>() / self.goals.len() as f32
+    }
+
+    /// Update the goal tracker after a successful action based on the corresponding goal type.
     /// Only the first goal with the same goal type and item type will be updated.
     ///
     /// # Arguments
-    /// * `result` - Result of the action performed.
     /// * `rhs_goal_type` - The goal type to be updated.
     /// * `rhs_item_type` - The item type of the goal.
+    /// * `removed_quantity` - The quantity of items removed or completed.
+    ///
+    /// # Returns
+    /// Result<GoalProgress, GoalError> - Err(GoalError::NotFound) if no goal matches,
+    /// Ok(GoalProgress) with the progress made by the updated goal otherwise.
     fn update(
         &mut self,
-        result: Result<(), LibError>,
         rhs_goal_type: GoalType,
         rhs_item_type: Option<Content>,
         removed_quantity: usize,
-    ) {
-        if result.is_ok() {
-            if let Some(goal) = self.goals.iter_mut().find(|goal| {
-                goal.goal_type == rhs_goal_type
-                    && match_content_type_variant(goal.item_type.clone(), rhs_item_type.clone())
-            }) {
-                println!("Found goal: {:?}", goal);
-                goal.items_left -= removed_quantity as u32;
-                goal.items_left = goal.items_left.max(0);
-                // if negative value, set to 0
-                if goal.items_left == 0 {
-                    goal.completed = true;
-                    self.completed_number += 1;
-                }
-            } else {
-                eprintln!("Error: Goal not found");
+    ) -> Result<GoalProgress, GoalError> {
+        let Some(index) = self.find_goal_index(&rhs_goal_type, &rhs_item_type) else {
+            return Err(GoalError::NotFound {
+                goal_type: rhs_goal_type,
+                item_type: rhs_item_type,
+            });
+        };
+        let goal = &mut self.goals[index];
+        let items_left_before = goal.items_left;
+        // removing more items than needed completes the goal without underflowing,
+        // an already completed goal is not counted again
+        let just_completed = goal.update_progress(removed_quantity);
+        let progress = GoalProgress {
+            goal_name: goal.name.clone(),
+            delta: items_left_before - goal.items_left,
+            items_left: goal.items_left,
+            just_completed,
+        };
+        if just_completed {
+            self.completed_number += 1;
+            self.notify_completed(index);
+        }
+        Ok(progress)
+    }
+
+    /// Update the first exploration goal with the tiles discovered in the map.
+    ///
+    /// The tiles discovered by the view functions of the enhanced map since the last
+    /// call are taken from the map, see `BobMap::take_discovered`, so call it after viewing.
+    ///
+    /// # Arguments
+    /// * `map` - The enhanced map updated by the view functions.
+    ///
+    /// # Returns
+    /// Result<GoalProgress, GoalError> - Err(GoalError::NotFound) if there is no exploration
+    /// goal, Ok(GoalProgress) with the progress made by the goal otherwise.
+    pub fn update_explored(&mut self, map: &mut BobMap) -> Result<GoalProgress, GoalError> {
+        self.update(GoalType::Explore, None, map.take_discovered())
+    }
+
+    /// Update the goals from an event emitted by robotics_lib, e.g. inside `handle_event`.
+    ///
+    /// Only `Event::AddedToBackpack` is handled: it updates the first `GetItems` goal for the
+    /// added content with the added quantity. The other events can't tell which goal they
+    /// complete (e.g. removing water from the backpack may or may not put out a fire), so
+    /// they are ignored.
+    ///
+    /// The actions of this module already update the goals, so don't use both for the
+    /// same items or they are counted twice.
+    ///
+    /// # Arguments
+    /// * `event` - The event emitted by robotics_lib.
+    ///
+    /// # Examples
+    /// ```
+    /// use robotics_lib::event::events::Event;
+    /// use robotics_lib::world::tile::Content;
+    /// use bob_lib::tracker::GoalTracker;
+    ///
+    /// let mut goal_tracker = GoalTracker::new();
+    /// let event = Event::AddedToBackpack(Content::Rock(0), 3);
+    /// if let Some(Ok(progress)) = goal_tracker.update_from_event(&event) {
+    ///     println!("{} rocks left", progress.items_left);
+    /// }
+    /// ```
+    ///
+    /// # Returns
+    /// Option<Result<GoalProgress, GoalError>> - None if the event is ignored, otherwise
+    /// the same result of `update_manual`.
+    pub fn update_from_event(&mut self, event: &Event) -> Option<Result<GoalProgress, GoalError>> {
+        match event {
+            Event::AddedToBackpack(content, quantity) => {
+                Some(self.update(GoalType::GetItems, Some(content.clone()), *quantity))
             }
-        } else if let Err(e) = result {
-            eprintln!("Error: {:?}", e);
+            _ => None,
         }
     }
 
@@ -260,24 +975,33 @@ impl GoalTracker {
     ///
     /// # Examples
     /// ```
-    /// use bob_lib::{GoalTracker, GoalType};
+    /// use robotics_lib::world::tile::Content;
+    /// use bob_lib::tracker::{Goal, GoalTracker, GoalType};
     ///
     /// let mut goal_tracker = GoalTracker::new();
-    /// // Assume a goal of type GetItems with some associated content
-    /// goal_tracker.update_manual(GoalType::GetItems, Some(Content::Rock), 3);
+    /// goal_tracker.add_goal(Goal::new(
+    ///     "rocks".to_string(),
+    ///     "collect some rocks".to_string(),
+    ///     GoalType::GetItems,
+    ///     Some(Content::Rock(0)),
+    ///     5,
+    /// ));
+    /// let progress = goal_tracker.update_manual(GoalType::GetItems, Some(Content::Rock(0)), 3);
+    /// assert_eq!(progress.unwrap().items_left, 2);
     /// ```
     ///
     /// This example demonstrates manually updating a goal of type `GetItems` with the removal of
     /// 3 items of type `Content::Rock`, in case the goal was not updated automatically, because an
     /// external tool(s) called the `put` or the `destroy` interface directly.
     ///
-    /// # Note
+    /// # Returns
     ///
-    /// If the goal is not found in the tracker, an error message is printed to the standard error.
+    /// Result<GoalProgress, GoalError> - Err(GoalError::NotFound) if no goal matches the goal
+    /// type and the item type, Ok(GoalProgress) with the progress made by the goal otherwise.
     ///
     /// # Panics
     ///
-    /// The method does not panic but may print an error message if the specified goal is not found.
+    /// The method does not panic.
     ///
     /// # Safety
     ///
@@ -288,16 +1012,8 @@ impl GoalTracker {
         goal_type: GoalType,
         item_type: Option<Content>,
         removed_quantity: usize,
-    ) {
-        if let Some(goal) = self.find_goal_mut(goal_type, item_type.clone()) {
-            println!("Found goal: {:?}", goal);
-            goal.update_progress(removed_quantity);
-            if goal.is_completed() {
-                self.completed_number += 1;
-            }
-        } else {
-            eprintln!("Error: Goal not found");
-        }
+    ) -> Result<GoalProgress, GoalError> {
+        self.update(goal_type, item_type, removed_quantity)
     }
 }
 
@@ -315,7 +1031,8 @@ impl Display for GoalTracker {
 /// Puts out a fire in a specified direction by using the robot to perform the action.
 /// It automatically checks if the robot is in front of a Fire and if the content is
 /// valid. If not, it returns an error. It does update all your goals if the action is successful.
-/// One unit of `Content::Water` from the backpack is used to put out the fire.
+/// One unit of `Content::Water` from the backpack is used to put out the fire, use
+/// `put_out_fire_with` to use more.
 ///
 /// # Arguments
 /// * `robot` - The robot that will perform the action.
@@ -324,42 +1041,99 @@ impl Display for GoalTracker {
 /// * `goal_tracker` - The goal tracker to update upon successfully putting out the fire.
 ///
 /// # Returns
-/// Result<(usize), LibError> - Ok((removed_quantity)) if the action is successful, Err(LibError) otherwise.
+/// Result<GoalProgress, ActionError> - Ok(GoalProgress) if the action is successful,
+/// Err(ActionError::Lib) if it failed, Err(ActionError::Goal) if no goal could be updated.
 ///
 pub fn put_out_fire(
     robot: &mut impl Runnable,
     world: &mut World,
     direction: Direction,
     goal_tracker: &mut GoalTracker,
-) -> Result<usize, LibError> {
+) -> Result<GoalProgress, ActionError> {
+    put_out_fire_with(robot, world, direction, 1, goal_tracker)
+}
+
+/// Puts out a fire in a specified direction using the given amount of water.
+/// It performs the same checks of `put_out_fire`, but `amount` units of `Content::Water`
+/// from the backpack are used to put out the fire.
+///
+/// # Arguments
+/// * `robot` - The robot that will perform the action.
+/// * `world` - The world in which the action takes place.
+/// * `direction` - The direction in which to perform the action.
+/// * `amount` - The units of water to use.
+/// * `goal_tracker` - The goal tracker to update upon successfully putting out the fire.
+///
+/// # Returns
+/// Result<GoalProgress, ActionError> - Ok(GoalProgress) if the action is successful,
+/// Err(ActionError::Lib) if it failed, with `LibError::OutOfBounds` if `direction` leaves the
+/// map, Err(ActionError::Goal) if no goal could be updated.
+///
+pub fn put_out_fire_with(
+    robot: &mut impl Runnable,
+    world: &mut World,
+    direction: Direction,
+    amount: usize,
+    goal_tracker: &mut GoalTracker,
+) -> Result<GoalProgress, ActionError> {
     // check if robot is in front of fire
-    match get_tile_in_direction(robot, world, &direction)
-        .unwrap()
-        .content
-    {
-        Content::Fire => {}
-        _ => {
-            let err = LibError::OperationNotAllowed;
-            eprintln!("Error: {:?}", err);
-            return Err(err);
-        }
+    match get_tile_in_direction(robot, world, &direction) {
+        Some(tile) => match tile.content {
+            Content::Fire => {}
+            _ => return Err(LibError::OperationNotAllowed.into()),
+        },
+        None => return Err(LibError::OutOfBounds.into()),
     }
 
     handle_put(
         robot,
         world,
         Content::Water(0),
-        1,
+        amount,
         direction,
         goal_tracker,
         GoalType::PutOutFire,
     )
-    // Ok(())
+}
+
+/// Puts out a fire next to the robot, in any direction. The fire is looked for with
+/// `find_direction_to`, then `put_out_fire` is called in its direction, so the same checks
+/// are done and your goals are updated.
+///
+/// # Arguments
+/// * `robot` - The robot that will perform the action.
+/// * `world` - The world in which the action takes place.
+/// * `goal_tracker` - The goal tracker to update upon successfully putting out the fire.
+///
+/// # Returns
+/// Result<GoalProgress, ActionError> - Err(ActionError::Lib) with
+/// `LibError::OperationNotAllowed` if there is no fire next to the robot, the same result of
+/// `put_out_fire` otherwise.
+///
+pub fn put_out_fire_anywhere(
+    robot: &mut impl Runnable,
+    world: &mut World,
+    goal_tracker: &mut GoalTracker,
+) -> Result<GoalProgress, ActionError> {
+    let direction = adjacent_direction(robot, world, |content| *content == Content::Fire)?;
+    put_out_fire(robot, world, direction, goal_tracker)
+}
+
+/// Returns the direction of the first tile next to the robot whose content satisfies `pred`,
+/// or `LibError::OperationNotAllowed` if there is none.
+fn adjacent_direction(
+    robot: &impl Runnable,
+    world: &World,
+    pred: impl Fn(&Content) -> bool,
+) -> Result<Direction, LibError> {
+    find_direction_to(robot, world, |tile| pred(&tile.content)).ok_or(LibError::OperationNotAllowed)
 }
 
 /// Sells items in a specified direction by using the robot to perform the action.
 /// It automatically checks if the robot is in front of a market and if the content to sell is
 /// valid. If not, it returns an error. It does update all your goals if the action is successful.
+/// It checks the backpack holds `quantity` of `content_in` before calling put, returning
+/// `LibError::NotEnoughContentInBackPack` if it doesn't.
 /// It calls the put interface internally from Robotics_lib.
 ///
 /// # Arguments
@@ -371,7 +1145,9 @@ pub fn put_out_fire(
 /// * `goal_tracker` - The goal tracker to update upon successfully selling items.
 ///
 /// # Returns
-/// Result<(usize), LibError> - Ok((removed_quantity)) if the action is successful, Err(LibError) otherwise.
+/// Result<GoalProgress, ActionError> - Ok(GoalProgress) if the action is successful,
+/// Err(ActionError::Lib) if it failed, with `LibError::OutOfBounds` if `direction` leaves the
+/// map, Err(ActionError::Goal) if no goal could be updated.
 ///
 pub fn sell_items_in_market(
     robot: &mut impl Runnable,
@@ -380,18 +1156,16 @@ pub fn sell_items_in_market(
     quantity: usize,
     direction: Direction,
     goal_tracker: &mut GoalTracker,
-) -> Result<usize, LibError> {
+) -> Result<GoalProgress, ActionError> {
     // check if the robot is in front of market
-    if let Some(tile) = get_tile_in_direction(robot, world, &direction) {
-        match tile.content {
+    match get_tile_in_direction(robot, world, &direction) {
+        Some(tile) => match tile.content {
             Content::Market(_) => {}
-            _ => {
-                let err = LibError::OperationNotAllowed;
-                eprintln!("Error: {:?}", err);
-                return Err(err);
-            }
-        }
+            _ => return Err(LibError::OperationNotAllowed.into()),
+        },
+        None => return Err(LibError::OutOfBounds.into()),
     }
+    check_backpack(&*robot, &content_in, quantity)?;
 
     handle_put(
         robot,
@@ -404,9 +1178,136 @@ pub fn sell_items_in_market(
     )
 }
 
+/// Returns how many items the market in a specified direction can still accept.
+/// It reads the tile in front of the robot, so `sell_items_in_market` can be called with a
+/// quantity the market can actually take.
+///
+/// # Arguments
+/// * `robot` - The robot facing the market.
+/// * `world` - The world in which the market is.
+/// * `direction` - The direction of the market.
+///
+/// # Returns
+/// Option<usize> - The remaining capacity of the market, None if the robot is not facing one.
+///
+pub fn market_remaining_capacity(
+    robot: &mut impl Runnable,
+    world: &mut World,
+    direction: Direction,
+) -> Option<usize> {
+    match get_tile_in_direction(robot, world, &direction)?.content {
+        Content::Market(capacity) => Some(capacity),
+        _ => None,
+    }
+}
+
+/// Puts items in a specified direction by using the robot to perform the action, e.g. rocks
+/// to build a street. Unlike the other actions it can be done on any tile, as long as `put`
+/// allows it. It does update all your goals if the action is successful.
+/// It checks the backpack holds `quantity` of `content_in` before calling put, returning
+/// `LibError::NotEnoughContentInBackPack` if it doesn't.
+/// It calls the put interface internally from Robotics_lib.
+///
+/// # Arguments
+/// * `robot` - The robot that will perform the action.
+/// * `world` - The world in which the action takes place.
+/// * `content_in` - The type of content to put.
+/// * `quantity` - The quantity of the content to put.
+/// * `direction` - The direction in which to perform the action.
+/// * `goal_tracker` - The goal tracker to update upon successfully putting items.
+///
+/// # Returns
+/// Result<GoalProgress, ActionError> - Ok(GoalProgress) if the action is successful,
+/// Err(ActionError::Lib) if it failed, Err(ActionError::Goal) if no goal could be updated.
+///
+pub fn put_and_track(
+    robot: &mut impl Runnable,
+    world: &mut World,
+    content_in: Content,
+    quantity: usize,
+    direction: Direction,
+    goal_tracker: &mut GoalTracker,
+) -> Result<GoalProgress, ActionError> {
+    check_backpack(&*robot, &content_in, quantity)?;
+
+    handle_put(
+        robot,
+        world,
+        content_in,
+        quantity,
+        direction,
+        goal_tracker,
+        GoalType::PutItems,
+    )
+}
+
+/// Sells as much of a content as possible in a specified direction: the quantity sold is the
+/// minimum between what the backpack holds and what the market in front of the robot can
+/// still accept, see `market_remaining_capacity`. It calls `sell_items_in_market` once, so the
+/// same checks are done and your goals are updated with the quantity actually sold.
+///
+/// # Arguments
+/// * `robot` - The robot that will perform the action.
+/// * `world` - The world in which the action takes place.
+/// * `direction` - The direction in which to perform the action.
+/// * `content_in` - The type of content to sell, only its variant is checked.
+/// * `goal_tracker` - The goal tracker to update upon successfully selling items.
+///
+/// # Returns
+/// Result<usize, ActionError> - Ok(total) with the quantity sold, Ok(0) if there is none of the
+/// content in the backpack or the market is full, Err(ActionError) as `sell_items_in_market`
+/// otherwise.
+///
+pub fn sell_best_effort(
+    robot: &mut impl Runnable,
+    world: &mut World,
+    direction: Direction,
+    content_in: Content,
+    goal_tracker: &mut GoalTracker,
+) -> Result<usize, ActionError> {
+    let available = content_in_backpack(&*robot, &content_in);
+    let market_capacity =
+        market_remaining_capacity(robot, world, direction.clone()).unwrap_or(usize::MAX);
+    let quantity = available.min(market_capacity);
+    if quantity == 0 {
+        return Ok(0);
+    }
+
+    sell_items_in_market(robot, world, content_in.clone(), quantity, direction, goal_tracker)?;
+    Ok(available.saturating_sub(content_in_backpack(&*robot, &content_in)))
+}
+
+/// Sells as much of a content as possible to a market next to the robot, in any direction.
+/// The market is looked for with `find_direction_to`, then `sell_best_effort` is called in its
+/// direction, so the same checks are done and your goals are updated.
+///
+/// # Arguments
+/// * `robot` - The robot that will perform the action.
+/// * `world` - The world in which the action takes place.
+/// * `content_in` - The type of content to sell, only its variant is checked.
+/// * `goal_tracker` - The goal tracker to update upon successfully selling items.
+///
+/// # Returns
+/// Result<usize, ActionError> - Err(ActionError::Lib) with `LibError::OperationNotAllowed`
+/// if there is no market next to the robot, the same result of `sell_best_effort` otherwise.
+///
+pub fn sell_anywhere(
+    robot: &mut impl Runnable,
+    world: &mut World,
+    content_in: Content,
+    goal_tracker: &mut GoalTracker,
+) -> Result<usize, ActionError> {
+    let direction = adjacent_direction(robot, world, |content| {
+        matches!(content, Content::Market(_))
+    })?;
+    sell_best_effort(robot, world, direction, content_in, goal_tracker)
+}
+
 /// Throws garbage in a specified direction by using the robot to perform the action.
 /// It automatically checks if the robot is in front of a Bin and if the content to throw is
 /// valid. If not, it returns an error. It does update all your goals if the action is successful.
+/// It checks the backpack holds `quantity` of `content_in` before calling put, returning
+/// `LibError::NotEnoughContentInBackPack` if it doesn't.
 /// It calls the put interface internally from Robotics_lib.
 ///
 ///
@@ -419,7 +1320,8 @@ pub fn sell_items_in_market(
 /// * `goal_tracker` - The goal tracker to update upon successfully throwing garbage.
 ///
 /// # Returns
-/// Result<(usize), LibError> - Ok((removed_quantity)) if the action is successful, Err(LibError) otherwise.
+/// Result<GoalProgress, ActionError> - Ok(GoalProgress) if the action is successful,
+/// Err(ActionError::Lib) if it failed, Err(ActionError::Goal) if no goal could be updated.
 ///
 pub fn throw_garbage(
     robot: &mut impl Runnable,
@@ -428,23 +1330,16 @@ pub fn throw_garbage(
     quantity: usize,
     direction: Direction,
     goal_tracker: &mut GoalTracker,
-) -> Result<usize, LibError> {
+) -> Result<GoalProgress, ActionError> {
     // check if the robot is in front of bin and content_in is garbage
     match get_tile_in_direction(robot, world, &direction) {
         Some(tile) => match tile.content {
             Content::Bin(_) => {}
-            _ => {
-                let err = LibError::OperationNotAllowed;
-                eprintln!("Error: {:?}", err);
-                return Err(err);
-            }
+            _ => return Err(LibError::OperationNotAllowed.into()),
         },
-        None => {
-            let err = LibError::OutOfBounds;
-            eprintln!("Error: {:?}", err);
-            return Err(err);
-        }
+        None => return Err(LibError::OutOfBounds.into()),
     }
+    check_backpack(&*robot, &content_in, quantity)?;
 
     handle_put(
         robot,
@@ -457,6 +1352,85 @@ pub fn throw_garbage(
     )
 }
 
+/// Throws all the garbage in the backpack, or as much as the Bin can take, in a specified
+/// direction. It reads the garbage in the backpack and the capacity of the Bin in front of the
+/// robot, then calls `throw_garbage`, so the same checks are done and your goals are updated.
+///
+/// # Arguments
+/// * `robot` - The robot that will perform the action.
+/// * `world` - The world in which the action takes place.
+/// * `direction` - The direction in which to perform the action.
+/// * `goal_tracker` - The goal tracker to update upon successfully throwing garbage.
+///
+/// # Returns
+/// Result<usize, ActionError> - Ok(total) with the quantity of garbage thrown, Ok(0) if there
+/// is no garbage in the backpack or the Bin is full, Err(ActionError) as `throw_garbage` otherwise.
+///
+pub fn throw_all_garbage(
+    robot: &mut impl Runnable,
+    world: &mut World,
+    direction: Direction,
+    goal_tracker: &mut GoalTracker,
+) -> Result<usize, ActionError> {
+    let garbage = garbage_in_backpack(&*robot);
+    let bin_capacity = match get_tile_in_direction(robot, world, &direction) {
+        // the range goes from the garbage already in the bin to its capacity
+        Some(Tile {
+            content: Content::Bin(range),
+            ..
+        }) => range.end.saturating_sub(range.start),
+        _ => usize::MAX,
+    };
+    let quantity = garbage.min(bin_capacity);
+    if quantity == 0 {
+        return Ok(0);
+    }
+
+    throw_garbage(robot, world, Content::Garbage(0), quantity, direction, goal_tracker)?;
+    Ok(garbage.saturating_sub(garbage_in_backpack(&*robot)))
+}
+
+/// Throws all the garbage in the backpack, or as much as the Bin can take, in a Bin next to
+/// the robot, in any direction. The Bin is looked for with `find_direction_to`, then
+/// `throw_all_garbage` is called in its direction, so the same checks are done and your goals
+/// are updated.
+///
+/// # Arguments
+/// * `robot` - The robot that will perform the action.
+/// * `world` - The world in which the action takes place.
+/// * `goal_tracker` - The goal tracker to update upon successfully throwing garbage.
+///
+/// # Returns
+/// Result<usize, ActionError> - Err(ActionError::Lib) with `LibError::OperationNotAllowed`
+/// if there is no Bin next to the robot, the same result of `throw_all_garbage` otherwise.
+///
+pub fn throw_garbage_anywhere(
+    robot: &mut impl Runnable,
+    world: &mut World,
+    goal_tracker: &mut GoalTracker,
+) -> Result<usize, ActionError> {
+    let direction = adjacent_direction(robot, world, |content| matches!(content, Content::Bin(_)))?;
+    throw_all_garbage(robot, world, direction, goal_tracker)
+}
+
+/// Returns the quantity of garbage in the backpack of the robot.
+fn garbage_in_backpack(robot: &impl Runnable) -> usize {
+    content_in_backpack(robot, &Content::Garbage(0))
+}
+
+/// Returns the quantity of a content in the backpack of the robot, only its variant is checked.
+fn content_in_backpack(robot: &impl Runnable, content: &Content) -> usize {
+    robot
+        .get_backpack()
+        .get_contents()
+        .iter()
+        .filter(|(item, _)| {
+            match_content_type_variant(Some((*item).clone()), Some(content.clone()))
+        })
+        .map(|(_, amount)| *amount)
+        .sum()
+}
+
 /// Gets items in a specified direction by using the robot to perform the action.
 /// It automatically checks if the robot is in front of a Content. If not, it returns an error.
 /// It does update all your goals if the action is successful.
@@ -470,7 +1444,8 @@ pub fn throw_garbage(
 /// * `item_type` - The type of item to get.
 ///
 /// # Returns
-/// Result<(usize), LibError> - Ok((removed_quantity)) if the action is successful, Err(LibError) otherwise.
+/// Result<GoalProgress, ActionError> - Ok(GoalProgress) if the action is successful,
+/// Err(ActionError::Lib) if it failed, Err(ActionError::Goal) if no goal could be updated.
 ///
 pub fn destroy_and_collect_item(
     robot: &mut impl Runnable,
@@ -478,14 +1453,80 @@ pub fn destroy_and_collect_item(
     direction: Direction,
     goal_tracker: &mut GoalTracker,
     item_type: Option<Content>,
-) -> Result<usize, LibError> {
-    match destroy(robot, world, direction) {
-        Ok(removed_quantity) => {
-            goal_tracker.update(Ok(()), GoalType::GetItems, item_type, removed_quantity);
-            Ok(removed_quantity)
+) -> Result<GoalProgress, ActionError> {
+    let removed_quantity = destroy(robot, world, direction)?;
+    goal_tracker
+        .update(GoalType::GetItems, item_type, removed_quantity)
+        .map_err(|error| ActionError::Goal {
+            error,
+            removed_quantity,
+        })
+}
+
+/// Gets items in a specified direction until the tile is empty or the backpack is full.
+/// It repeatedly calls the destroy interface from Robotics_lib, updating your goals after
+/// every successful call, like `destroy_and_collect_item`.
+/// It stops cleanly when the backpack is full, i.e. on `LibError::NotEnoughSpace`, or when
+/// there is nothing left to destroy, i.e. on `LibError::NoContent`.
+///
+/// # Arguments
+/// * `robot` - The robot that will perform the action.
+/// * `world` - The world in which the action takes place.
+/// * `direction` - The direction in which to perform the action.
+/// * `goal_tracker` - The goal tracker to update upon successfully getting items.
+/// * `item_type` - The type of item to get.
+///
+/// # Returns
+/// Result<usize, ActionError> - Ok(total) with the total quantity collected if the action is
+/// successful, Err(ActionError::Lib) if destroy failed for any other reason,
+/// Err(ActionError::Goal) with the total quantity collected if no goal could be updated.
+///
+pub fn collect_until_full(
+    robot: &mut impl Runnable,
+    world: &mut World,
+    direction: Direction,
+    goal_tracker: &mut GoalTracker,
+    item_type: Option<Content>,
+) -> Result<usize, ActionError> {
+    let mut total = 0;
+
+    loop {
+        let backpack = robot.get_backpack();
+        if backpack.get_contents().values().sum::<usize>() >= backpack.get_size() {
+            break;
         }
-        Err(err) => Err(err),
+
+        let removed_quantity = match destroy(robot, world, direction.clone()) {
+            Ok(0) => break,
+            Ok(removed_quantity) => removed_quantity,
+            Err(LibError::NotEnoughSpace(_)) | Err(LibError::NoContent) => break,
+            Err(err) => return Err(err.into()),
+        };
+        total += removed_quantity;
+
+        let result = goal_tracker.update(GoalType::GetItems, item_type.clone(), removed_quantity);
+        if let Err(error) = result {
+            return Err(ActionError::Goal {
+                error,
+                removed_quantity: total,
+            });
+        }
+    }
+
+    Ok(total)
+}
+
+/// Checks that the backpack of the robot holds at least `quantity` of `content`.
+/// Returns Err(LibError::NotEnoughContentInBackPack) otherwise.
+fn check_backpack(
+    robot: &impl Runnable,
+    content: &Content,
+    quantity: usize,
+) -> Result<(), LibError> {
+    if content_in_backpack(robot, content) < quantity {
+        return Err(LibError::NotEnoughContentInBackPack);
     }
+    Ok(())
 }
 
 fn handle_put(
@@ -496,12 +1537,12 @@ fn handle_put(
     direction: Direction,
     goal_tracker: &mut GoalTracker,
     goal_type: GoalType,
-) -> Result<usize, LibError> {
-    match put(robot, world, content_in.clone(), quantity, direction) {
-        Ok(removed_quantity) => {
-            goal_tracker.update(Ok(()), goal_type, Some(content_in), removed_quantity);
-            Ok(removed_quantity)
-        }
-        Err(err) => Err(err),
-    }
+) -> Result<GoalProgress, ActionError> {
+    let removed_quantity = put(robot, world, content_in.clone(), quantity, direction)?;
+    goal_tracker
+        .update(goal_type, Some(content_in), removed_quantity)
+        .map_err(|error| ActionError::Goal {
+            error,
+            removed_quantity,
+        })
 }
@@ -2,7 +2,7 @@ use std::mem::discriminant;
 use robotics_lib::interface::{Direction, robot_view};
 use robotics_lib::runner::Runnable;
 
-use robotics_lib::world::tile::{Content, Tile};
+use robotics_lib::world::tile::{Content, Tile, TileType};
 use robotics_lib::world::World;
 
 pub fn match_content_type_variant(lhs: Option<Content>, rhs: Option<Content>) -> bool {
@@ -18,21 +18,159 @@ pub fn get_tile_in_direction(
     direction: &Direction,
 ) -> Option<Tile> {
     let view = robot_view(robot, world);
-    let center = (view.len() / 2, view[0].len() / 2);
-    let (mut row, mut col) = center;
-
-    match direction {
-        Direction::Up => row -= 1,
-        Direction::Down => row += 1,
-        Direction::Left => col -= 1,
-        Direction::Right => col += 1,
+    tile_in_view_direction(&view, direction).cloned()
+}
+
+/// Returns the tile next to the robot in `direction`, read from a view already returned by
+/// `robot_view`, so the view isn't requested again and no mutable access is needed
+///
+/// The robot is in the center of the view. It returns [None] if the tile is undiscovered
+/// or outside the view, e.g. looking up from the first row of the map
+pub fn tile_in_view_direction(view: &[Vec<Option<Tile>>], direction: &Direction) -> Option<&Tile> {
+    let (row, col) = (view.len() / 2, view.first()?.len() / 2);
+
+    // the neighbour may be outside the view when the robot is on the border
+    let (row, col) = match direction {
+        Direction::Up => (row.checked_sub(1)?, col),
+        Direction::Down => (row + 1, col),
+        Direction::Left => (row, col.checked_sub(1)?),
+        Direction::Right => (row, col + 1),
+    };
+
+    view.get(row)?.get(col)?.as_ref()
+}
+
+/// Returns the first direction, among up, down, left and right, whose tile next to the robot
+/// satisfies `pred`, e.g. to find where a fire or a bin is before acting on it
+///
+/// The view is requested once, see [direction_in_view]
+pub fn find_direction_to(
+    robot: &impl Runnable,
+    world: &World,
+    pred: impl Fn(&Tile) -> bool,
+) -> Option<Direction> {
+    let view = robot_view(robot, world);
+    direction_in_view(&view, pred)
+}
+
+/// Returns the first direction, among up, down, left and right, whose tile next to the robot
+/// satisfies `pred`, read from a view already returned by `robot_view`
+///
+/// Undiscovered tiles and tiles outside the view never satisfy `pred`
+pub fn direction_in_view(
+    view: &[Vec<Option<Tile>>],
+    pred: impl Fn(&Tile) -> bool,
+) -> Option<Direction> {
+    [Direction::Up, Direction::Down, Direction::Left, Direction::Right]
+        .into_iter()
+        .find(|direction| tile_in_view_direction(view, direction).is_some_and(&pred))
+}
+
+/// Returns the cost of stepping onto a tile as `(energy, rocks)`
+///
+/// A negative rocks cost means that crossing the tile yields rocks, like when
+/// digging through a [TileType::Mountain]. Deep water and lava need a street
+/// to be built on them, which is paid in rocks.
+///
+/// It returns [None] for tiles that can't be entered: walls and tiles blocked by
+/// their content (markets, banks, bins, crates and buildings)
+pub fn get_edge_cost(tile: &Tile) -> Option<(isize, isize)> {
+    match tile.content {
+        Content::Market(_)
+        | Content::Bank(_)
+        | Content::Bin(_)
+        | Content::Crate(_)
+        | Content::Building => return None,
+        _ => {}
     }
 
-    if let Some(tile) = view.get(row) {
-        if let Some(tile) = tile.get(col) {
-            return tile.clone();
-        }
+    match tile.tile_type {
+        TileType::Street => Some((1, 0)),
+        TileType::Grass => Some((2, 0)),
+        TileType::Sand => Some((3, 0)),
+        TileType::Snow => Some((4, 0)),
+        TileType::ShallowWater => Some((5, 0)),
+        TileType::Hill => Some((6, 0)),
+        TileType::DeepWater => Some((3, 2)),
+        TileType::Lava => Some((3, 3)),
+        TileType::Mountain => Some((10, -4)),
+        TileType::Teleport(_) => Some((1, 0)),
+        TileType::Wall => None,
     }
+}
+
+/// Returns the cost of stepping from a tile onto an adjacent one as `(energy, rocks)`
+///
+/// It is the cost of [get_edge_cost] plus an uphill penalty, the square of the
+/// elevation difference, going downhill doesn't cost anything more
+///
+/// It returns [None] if the destination tile can't be entered
+pub fn get_edge_cost_with_elevation(from: &Tile, to: &Tile) -> Option<(isize, isize)> {
+    let (energy, rocks) = get_edge_cost(to)?;
+    let climb = to.elevation.saturating_sub(from.elevation) as isize;
+    Some((energy + climb.pow(2), rocks))
+}
 
-    None
-}
\ No newline at end of file
+/// Returns the cost of stepping from a tile onto a diagonally adjacent one as
+/// `(energy, rocks)`
+///
+/// The energy is √2 times the average of the [get_edge_cost] of the two tiles, rounded up,
+/// plus the same uphill penalty of [get_edge_cost_with_elevation]. The rocks are the ones
+/// of the destination tile, like for orthogonal steps
+///
+/// It returns [None] if the destination tile can't be entered
+pub fn get_diagonal_edge_cost(from: &Tile, to: &Tile) -> Option<(isize, isize)> {
+    let (to_energy, rocks) = get_edge_cost(to)?;
+    let from_energy = get_edge_cost(from).map_or(to_energy, |(energy, _)| energy);
+    let average = (from_energy + to_energy) as f64 / 2.0;
+    let climb = to.elevation.saturating_sub(from.elevation) as isize;
+    Some(((average * std::f64::consts::SQRT_2).ceil() as isize + climb.pow(2), rocks))
+}
+
+/// Returns how much one rock is worth in energy, given the available energy and rocks
+///
+/// The result is never lower than 1, so it can always be used as a divider
+pub fn costs_relation(energy: usize, rocks: usize) -> f64 {
+    (energy.max(1) as f64 / rocks.max(1) as f64).max(1.0)
+}
+
+/// Returns the Manhattan distance between two coordinates, the number of steps needed
+/// to go from `a` to `b` moving only up, down, left and right
+pub fn manhattan_distance(a: (usize, usize), b: (usize, usize)) -> usize {
+    a.0.abs_diff(b.0).saturating_add(a.1.abs_diff(b.1))
+}
+
+/// Returns the Chebyshev distance between two coordinates, the number of steps needed
+/// to go from `a` to `b` moving diagonally too
+pub fn chebyshev_distance(a: (usize, usize), b: (usize, usize)) -> usize {
+    a.0.abs_diff(b.0).max(a.1.abs_diff(b.1))
+}
+
+/// Returns the [Direction] to go from `from` to `to`, e.g. to drive `go`
+///
+/// It returns [None] if the coordinates are not orthogonally adjacent
+pub fn direction_between(from: (usize, usize), to: (usize, usize)) -> Option<Direction> {
+    match (to.0 as isize - from.0 as isize, to.1 as isize - from.1 as isize) {
+        (-1, 0) => Some(Direction::Up),
+        (1, 0) => Some(Direction::Down),
+        (0, -1) => Some(Direction::Left),
+        (0, 1) => Some(Direction::Right),
+        _ => None,
+    }
+}
+
+/// Prints a grid of tiles as a table, one row of the grid per line, showing the type of
+/// every tile and its content, if any
+pub fn pretty_print_tilemap(tiles: Vec<Vec<Tile>>) {
+    for row in tiles {
+        let mut row_str = String::new();
+        for tile in row {
+            let content_display = match tile.content {
+                Content::None => format!("{:?}", tile.tile_type),
+                _ => format!("{:?}({})", tile.tile_type, tile.content),
+            };
+            row_str += &format!("| {:<8}\t", content_display);
+        }
+        println!("{}|", row_str);
+    }
+}
@@ -0,0 +1,85 @@
+//! Harness shared by the tests which need a [World] and a running robot
+#![allow(dead_code)]
+
+use std::collections::HashMap;
+
+use robotics_lib::runner::Runner;
+use robotics_lib::world::environmental_conditions::{EnvironmentalConditions, WeatherType};
+use robotics_lib::world::tile::{Content, Tile, TileType};
+use robotics_lib::world::world_generator::{Generator, WorldGeneratorResult};
+use robotics_lib::world::World;
+
+use bob_lib::enhanced_map::BobMap;
+use bob_lib::robot::{BobBody, BobRobot};
+use bob_lib::tracker::GoalTracker;
+
+/// Generator of a grass world with the robot at the given coordinates, some of its tiles
+/// can be changed
+pub struct TestWorld {
+    size: usize,
+    spawn: (usize, usize),
+    tiles: Vec<((usize, usize), Tile)>,
+}
+
+impl TestWorld {
+    pub fn new(size: usize, spawn: (usize, usize)) -> TestWorld {
+        TestWorld {
+            size,
+            spawn,
+            tiles: vec![],
+        }
+    }
+
+    /// Function to change the tile at the given coordinates
+    pub fn with_tile(
+        mut self,
+        coordinate: (usize, usize),
+        tile_type: TileType,
+        content: Content,
+    ) -> TestWorld {
+        let tile = Tile {
+            tile_type,
+            content,
+            elevation: 0,
+        };
+        self.tiles.push((coordinate, tile));
+        self
+    }
+}
+
+impl Generator for TestWorld {
+    fn gen(&mut self) -> WorldGeneratorResult {
+        let grass = Tile {
+            tile_type: TileType::Grass,
+            content: Content::None,
+            elevation: 0,
+        };
+        let mut tiles = vec![vec![grass; self.size]; self.size];
+        for ((x, y), tile) in self.tiles.iter() {
+            tiles[*x][*y] = tile.clone();
+        }
+        let conditions = EnvironmentalConditions::new(&[WeatherType::Sunny], 15, 12).unwrap();
+        (tiles, self.spawn, conditions, 100.0, Some(HashMap::new()))
+    }
+}
+
+/// Function to run `ticks` ticks of `world` with a [BobRobot] running `logic`
+pub fn run_ticks(
+    mut world: TestWorld,
+    ticks: usize,
+    logic: impl FnMut(&mut BobBody, &mut BobMap, &mut GoalTracker, &mut World) + 'static,
+) {
+    let robot = BobRobot::new(Box::new(logic));
+    let mut runner = Runner::new(Box::new(robot), &mut world).unwrap();
+    for _ in 0..ticks {
+        runner.game_tick().unwrap();
+    }
+}
+
+/// Function to run a single tick of `world` with a [BobRobot] running `logic`
+pub fn run_tick(
+    world: TestWorld,
+    logic: impl FnMut(&mut BobBody, &mut BobMap, &mut GoalTracker, &mut World) + 'static,
+) {
+    run_ticks(world, 1, logic);
+}
@@ -0,0 +1,577 @@
+#[cfg(test)]
+mod tests {
+    use std::thread;
+
+    use robotics_lib::world::tile::{Content, Tile, TileType};
+
+    use bob_lib::enhanced_map::{bob_type_check, BobErr, BobMap, BobPinTypes, SharedBobMap};
+    use bob_lib::PlannerOptions;
+
+    fn grass_tiles(size: usize) -> Vec<Vec<Option<Tile>>> {
+        let tile = Tile {
+            tile_type: TileType::Grass,
+            content: Content::None,
+            elevation: 0,
+        };
+        vec![vec![Some(tile); size]; size]
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_save_and_load() {
+        let mut map = BobMap::from_tiles(grass_tiles(4));
+        map.add_pin(BobPinTypes::City, (0, 1)).ok().unwrap();
+        map.add_pin(BobPinTypes::Bank(3), (2, 2)).ok().unwrap();
+        map.add_pin(BobPinTypes::Contents(Content::Fish(2)), (3, 0)).ok().unwrap();
+
+        let path = std::env::temp_dir().join("bob_lib_test_save_and_load.bin");
+        map.save(&path).unwrap();
+        let loaded = BobMap::load(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded.search_pin(BobPinTypes::City).ok(), Some(vec![(0, 1)]));
+        assert_eq!(loaded.search_pin(BobPinTypes::Bank(3)).ok(), Some(vec![(2, 2)]));
+        assert_eq!(
+            loaded.search_pin(BobPinTypes::Contents(Content::Fish(2))).ok(),
+            Some(vec![(3, 0)])
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_load_out_of_bounds() {
+        // same layout of a saved map: tiles, pins and notes
+        let no_pins: Vec<((usize, usize), BobPinTypes)> = vec![];
+        let no_notes: Vec<((usize, usize), String)> = vec![];
+        let saved_pin = (grass_tiles(2), vec![((0, 2), BobPinTypes::City)], no_notes);
+        let saved_note = (grass_tiles(2), no_pins, vec![((2, 1), "far away".to_string())]);
+
+        let path = std::env::temp_dir().join("bob_lib_test_load_out_of_bounds.bin");
+        std::fs::write(&path, bincode::serialize(&saved_pin).unwrap()).unwrap();
+        let pin_err = BobMap::load(&path).err().unwrap();
+        std::fs::write(&path, bincode::serialize(&saved_note).unwrap()).unwrap();
+        let note_err = BobMap::load(&path).err().unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(pin_err.kind(), std::io::ErrorKind::InvalidData);
+        assert_eq!(note_err.kind(), std::io::ErrorKind::InvalidData);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_pin_serde_round_trip() {
+        let pins = [
+            BobPinTypes::TileType(TileType::Sand),
+            BobPinTypes::Contents(Content::Rock(4)),
+        ];
+        for pin in pins {
+            let bytes = bincode::serialize(&pin).unwrap();
+            let decoded: BobPinTypes = bincode::deserialize(&bytes).unwrap();
+            assert_eq!(decoded, pin);
+        }
+
+        let custom = BobPinTypes::Custom(std::sync::Arc::new(5));
+        assert!(bincode::serialize(&custom).is_err());
+    }
+
+    #[cfg(feature = "image")]
+    #[test]
+    fn test_export_image() {
+        let mut tiles = grass_tiles(3);
+        tiles[2][2] = None;
+        let mut map = BobMap::from_tiles(tiles);
+        map.add_pin(BobPinTypes::Market, (0, 1)).ok().unwrap();
+
+        let path = std::env::temp_dir().join("bob_lib_test_export_image.png");
+        map.export_image(&path).unwrap();
+        let decoder = png::Decoder::new(std::fs::File::open(&path).unwrap());
+        let mut reader = decoder.read_info().unwrap();
+        let mut pixels = vec![0; reader.output_buffer_size()];
+        reader.next_frame(&mut pixels).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!((reader.info().width, reader.info().height), (3, 3));
+        assert_ne!(pixels[0..4], pixels[4..8]);
+        assert_eq!(pixels[32..36], [0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn test_export_pins_csv() {
+        let mut map = BobMap::from_tiles(grass_tiles(3));
+        map.add_pin(BobPinTypes::Contents(Content::Rock(2)), (1, 2)).ok().unwrap();
+        map.add_pin(BobPinTypes::Custom(std::sync::Arc::new(5)), (2, 0)).ok().unwrap();
+
+        let path = std::env::temp_dir().join("bob_lib_test_export_pins.csv");
+        map.export_pins_csv(&path).unwrap();
+        let csv = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(csv, "x,y,pin_type,pin_value\n1,2,Contents,Rock(2)\n");
+    }
+
+    #[test]
+    fn test_multiple_pins() {
+        let mut map = BobMap::from_tiles(grass_tiles(3));
+        map.add_pin(BobPinTypes::Market, (1, 1)).ok().unwrap();
+        assert!(map.add_pin(BobPinTypes::Bank(2), (1, 1)).is_err());
+        map.add_pin_multi(BobPinTypes::Bank(2), (1, 1)).ok().unwrap();
+
+        let pins = map.get_pins((1, 1));
+        assert_eq!(*pins[0], BobPinTypes::Market);
+        assert_eq!(*pins[1], BobPinTypes::Bank(2));
+
+        let deleted = map.delete_pin_matching((1, 1), |pin| *pin == BobPinTypes::Market).ok().unwrap();
+        assert_eq!(*deleted, BobPinTypes::Market);
+        assert_eq!(map.get_pins((1, 1)).len(), 1);
+        assert!(map.search_pin(BobPinTypes::Market).is_err());
+        assert_eq!(map.search_pin(BobPinTypes::Bank(2)).ok(), Some(vec![(1, 1)]));
+    }
+
+    #[test]
+    fn test_move_pin() {
+        let mut map = BobMap::from_tiles(grass_tiles(3));
+        map.add_pin(BobPinTypes::Market, (0, 0)).ok().unwrap();
+        map.add_pin(BobPinTypes::City, (2, 2)).ok().unwrap();
+        let pin = map.get_pin((0, 0)).unwrap();
+
+        assert!(map.move_pin((1, 1), (1, 2)).is_err());
+        assert!(map.move_pin((0, 0), (2, 2)).is_err());
+        assert!(map.move_pin((0, 0), (0, 1)).is_ok());
+
+        assert!(map.get_pin((0, 0)).is_none());
+        assert!(std::sync::Arc::ptr_eq(&map.get_pin((0, 1)).unwrap(), &pin));
+        assert_eq!(map.search_pin(BobPinTypes::Market).ok(), Some(vec![(0, 1)]));
+    }
+
+    #[test]
+    fn test_search_deleted_pin() {
+        let mut map = BobMap::from_tiles(grass_tiles(3));
+        map.add_pin(BobPinTypes::Market, (0, 0)).ok().unwrap();
+        map.add_pin(BobPinTypes::Market, (2, 2)).ok().unwrap();
+
+        map.delete_pin((0, 0)).ok().unwrap();
+        assert_eq!(map.search_pin(BobPinTypes::Market).ok(), Some(vec![(2, 2)]));
+
+        map.delete_pin((2, 2)).ok().unwrap();
+        assert!(matches!(map.search_pin(BobPinTypes::Market), Err(BobErr::PinNotFound)));
+    }
+
+    #[test]
+    fn test_iter_pins() {
+        let mut map = BobMap::from_tiles(grass_tiles(3));
+        map.add_pin(BobPinTypes::City, (2, 0)).ok().unwrap();
+        map.add_pin(BobPinTypes::Market, (0, 2)).ok().unwrap();
+        map.add_pin_multi(BobPinTypes::Bank(1), (0, 2)).ok().unwrap();
+        map.add_pin(BobPinTypes::I32(7), (0, 1)).ok().unwrap();
+
+        let pins: Vec<((usize, usize), BobPinTypes)> =
+            map.iter_pins().into_iter().map(|(c, pin)| (c, (*pin).clone())).collect();
+        assert_eq!(
+            pins,
+            vec![
+                ((0, 1), BobPinTypes::I32(7)),
+                ((0, 2), BobPinTypes::Market),
+                ((0, 2), BobPinTypes::Bank(1)),
+                ((2, 0), BobPinTypes::City),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_nearest_pin() {
+        let mut map = BobMap::from_tiles(grass_tiles(5));
+        assert!(map.nearest_pin((0, 0), BobPinTypes::Market).is_none());
+
+        map.add_pin(BobPinTypes::Market, (4, 4)).ok().unwrap();
+        map.add_pin(BobPinTypes::Market, (1, 3)).ok().unwrap();
+        map.add_pin(BobPinTypes::Market, (3, 1)).ok().unwrap();
+
+        assert_eq!(map.nearest_pin((0, 0), BobPinTypes::Market), Some(((1, 3), 4)));
+        assert_eq!(map.nearest_pin((4, 3), BobPinTypes::Market), Some(((4, 4), 1)));
+    }
+
+    #[test]
+    fn test_route_to_nearest_pin() {
+        let options = PlannerOptions::energy_save().with_max_energy(100);
+        let mut tiles = grass_tiles(5);
+        // markets and banks can't be entered, the bank is walled in too
+        for (x, y) in [(0, 3), (3, 0)] {
+            tiles[x][y].as_mut().unwrap().content = Content::Market(5);
+        }
+        tiles[4][4].as_mut().unwrap().content = Content::Bank(0..10);
+        tiles[3][4].as_mut().unwrap().tile_type = TileType::Wall;
+        tiles[4][3].as_mut().unwrap().tile_type = TileType::Wall;
+        let mut map = BobMap::from_tiles(tiles);
+        map.add_pin(BobPinTypes::Market, (3, 0)).ok().unwrap();
+        map.add_pin(BobPinTypes::Market, (0, 3)).ok().unwrap();
+        map.add_pin(BobPinTypes::Bank(0), (4, 4)).ok().unwrap();
+
+        // both markets are two steps away, the one with the lowest coordinates wins
+        let market = BobPinTypes::Market;
+        let (path, cost) = map.route_to_nearest_pin((0, 0), market.clone(), &options).unwrap();
+        assert_eq!(path, vec![(0, 0), (0, 1), (0, 2)]);
+        assert_eq!(cost.energy, 4);
+
+        let (path, _) = map.route_to_nearest_pin((4, 0), market, &options).unwrap();
+        assert_eq!(path, vec![(4, 0)]);
+
+        assert!(map.route_to_nearest_pin((0, 0), BobPinTypes::Bank(0), &options).is_none());
+        assert!(map.route_to_nearest_pin((0, 0), BobPinTypes::City, &options).is_none());
+    }
+
+    #[test]
+    fn test_route_to_nearest_pin_walkable() {
+        let options = PlannerOptions::energy_save().with_max_energy(100);
+        let mut map = BobMap::from_tiles(grass_tiles(3));
+        map.add_pin(BobPinTypes::City, (2, 2)).ok().unwrap();
+
+        // a grass tile can be entered, so the path ends on the pin
+        let (path, cost) = map.route_to_nearest_pin((0, 0), BobPinTypes::City, &options).unwrap();
+        assert_eq!(path.last(), Some(&(2, 2)));
+        assert_eq!(cost.energy, 8);
+    }
+
+    #[test]
+    fn test_route_to_nearest_pin_tie() {
+        let options = PlannerOptions::energy_save().with_max_energy(100);
+        let mut tiles = grass_tiles(3);
+        tiles[1][1].as_mut().unwrap().content = Content::Market(5);
+        let mut map = BobMap::from_tiles(tiles);
+        map.add_pin(BobPinTypes::Market, (1, 1)).ok().unwrap();
+
+        // (0, 1) and (1, 0) are one step away, the lowest coordinates win
+        let (path, _) = map.route_to_nearest_pin((0, 0), BobPinTypes::Market, &options).unwrap();
+        assert_eq!(path, vec![(0, 0), (0, 1)]);
+    }
+
+    #[test]
+    fn test_count_pin() {
+        let mut map = BobMap::from_tiles(grass_tiles(3));
+        map.add_pin(BobPinTypes::Bank(3), (0, 0)).ok().unwrap();
+        map.add_pin(BobPinTypes::Bank(3), (1, 1)).ok().unwrap();
+        map.add_pin(BobPinTypes::Bank(5), (2, 2)).ok().unwrap();
+
+        assert_eq!(map.count_pin(&BobPinTypes::Bank(3)), 2);
+        assert_eq!(map.count_pin(&BobPinTypes::Bank(5)), 1);
+        assert_eq!(map.count_pin(&BobPinTypes::Market), 0);
+
+        let histogram = map.pin_type_histogram();
+        assert_eq!(histogram.len(), 2);
+        assert_eq!(histogram[&BobPinTypes::Bank(3)], 2);
+    }
+
+    #[test]
+    fn test_pin_notes() {
+        let mut map = BobMap::from_tiles(grass_tiles(3));
+        let note = "market sells fish cheap".to_string();
+        map.add_pin_with_note(BobPinTypes::Market, (1, 2), note).ok().unwrap();
+
+        assert_eq!(map.get_note((1, 2)), Some("market sells fish cheap"));
+        assert_eq!(map.get_note((0, 0)), None);
+
+        map.move_pin((1, 2), (0, 0)).ok().unwrap();
+        assert_eq!(map.get_note((1, 2)), None);
+        assert_eq!(map.get_note((0, 0)), Some("market sells fish cheap"));
+
+        map.delete_pin((0, 0)).ok().unwrap();
+        assert_eq!(map.get_note((0, 0)), None);
+    }
+
+    #[test]
+    fn test_update_with_diff() {
+        let mut tiles = grass_tiles(3);
+        tiles[2][2] = None;
+        let mut map = BobMap::from_tiles(tiles);
+
+        let grass = grass_tiles(1)[0][0].clone().unwrap();
+        let sand = Tile {
+            tile_type: TileType::Sand,
+            ..grass.clone()
+        };
+
+        // the same tile seen again is not a change
+        let update = vec![(0, 0, grass.clone()), (1, 1, sand), (2, 2, grass)];
+        let changed = map.update_with_diff(update);
+        assert_eq!(changed, vec![(1, 1), (2, 2)]);
+        assert!(map.last_diff().is_empty());
+    }
+
+    #[test]
+    fn test_get_tile() {
+        let mut tiles = grass_tiles(3);
+        tiles[1][1] = None;
+        let mut map = BobMap::from_tiles(tiles);
+
+        assert_eq!(map.get_tile((0, 2)).map(|tile| &tile.tile_type), Some(&TileType::Grass));
+        assert!(map.get_tile((1, 1)).is_none());
+        assert!(map.get_tile((3, 0)).is_none());
+        assert!(map.get_tile((0, 3)).is_none());
+
+        map.get_tile_mut((0, 2)).unwrap().content = Content::Rock(2);
+        assert_eq!(map.get_tile((0, 2)).unwrap().content, Content::Rock(2));
+        assert!(map.get_tile_mut((1, 1)).is_none());
+    }
+
+    #[test]
+    fn test_discovered_ratio() {
+        let mut tiles = grass_tiles(4);
+        for row in tiles.iter_mut().take(2) {
+            row.fill(None);
+        }
+        let map = BobMap::from_tiles(tiles);
+
+        assert_eq!(map.discovered_count(), 8);
+        assert_eq!(map.discovered_ratio(), 0.5);
+        assert_eq!(BobMap::from_tiles(vec![]).discovered_ratio(), 0.0);
+    }
+
+    fn seeded_map() -> BobMap {
+        let mut tiles = grass_tiles(4);
+        tiles[0][3] = None;
+        tiles[1][2].as_mut().unwrap().tile_type = TileType::Lava;
+        tiles[3][0].as_mut().unwrap().tile_type = TileType::Lava;
+        tiles[2][1].as_mut().unwrap().content = Content::Coin(3);
+        BobMap::from_tiles(tiles)
+    }
+
+    #[test]
+    fn test_tiles_in_box() {
+        let map = seeded_map();
+
+        let coordinates: Vec<(usize, usize)> =
+            map.tiles_in_box((0, 2), (1, 5)).into_iter().map(|(c, _)| c).collect();
+        assert_eq!(coordinates, vec![(0, 2), (1, 2), (1, 3)]);
+
+        let tiles = map.tiles_in_box((1, 1), (2, 2));
+        assert_eq!(tiles.len(), 4);
+        assert_eq!(tiles[1].0, (1, 2));
+        assert_eq!(tiles[1].1.tile_type, TileType::Lava);
+        assert!(map.tiles_in_box((5, 5), (7, 7)).is_empty());
+    }
+
+    #[test]
+    fn test_find_tiles() {
+        let map = seeded_map();
+
+        assert_eq!(map.find_tiles(|tile| tile.tile_type == TileType::Lava), vec![(1, 2), (3, 0)]);
+        assert_eq!(map.find_tiles(|tile| matches!(tile.content, Content::Coin(_))), vec![(2, 1)]);
+        assert_eq!(map.find_tiles(|_| true).len(), 15);
+    }
+
+    #[test]
+    fn test_exploration_frontier() {
+        let mut tiles = vec![vec![None; 5]; 5];
+        // a 3x3 island in the middle of the unknown
+        for row in tiles.iter_mut().take(4).skip(1) {
+            for tile in row.iter_mut().take(4).skip(1) {
+                *tile = grass_tiles(1)[0][0].clone();
+            }
+        }
+        let map = BobMap::from_tiles(tiles);
+
+        let frontier = map.exploration_frontier();
+        assert_eq!(frontier.len(), 8);
+        assert!(!frontier.contains(&(2, 2)));
+        assert!(frontier.contains(&(1, 1)));
+
+        // the border of the map is not unknown
+        assert!(BobMap::from_tiles(grass_tiles(3)).exploration_frontier().is_empty());
+    }
+
+    #[test]
+    fn test_iter() {
+        let mut map = seeded_map();
+        map.add_pin(BobPinTypes::Market, (2, 1)).ok().unwrap();
+
+        let discovered = map.iter().filter(|(_, _, tile, _)| tile.is_some()).count();
+        assert_eq!(discovered, 15);
+        assert_eq!(map.iter().count(), 16);
+
+        let pinned: Vec<(usize, usize)> =
+            map.iter().filter(|(_, _, _, pin)| pin.is_some()).map(|(x, y, _, _)| (x, y)).collect();
+        assert_eq!(pinned, vec![(2, 1)]);
+        assert_eq!(map.iter().nth(3).map(|(x, y, _, _)| (x, y)), Some((0, 3)));
+    }
+
+    #[test]
+    fn test_dimensions() {
+        let map = BobMap::from_tiles(vec![grass_tiles(5)[0].clone(); 3]);
+        assert_eq!(map.dimensions(), (3, 5));
+        assert!(map.contains((2, 4)));
+        assert!(!map.contains((3, 0)));
+        assert!(!map.contains((0, 5)));
+
+        assert_eq!(BobMap::from_tiles(vec![]).dimensions(), (0, 0));
+    }
+
+    #[test]
+    fn test_elevation_delta() {
+        let mut tiles = grass_tiles(3);
+        tiles[0][1].as_mut().unwrap().elevation = 4;
+        tiles[1][1].as_mut().unwrap().elevation = 1;
+        tiles[2][2] = None;
+        let map = BobMap::from_tiles(tiles);
+
+        assert_eq!(map.elevation_delta((0, 0), (0, 1)), Some(4));
+        assert_eq!(map.elevation_delta((0, 1), (1, 1)), Some(-3));
+        assert_eq!(map.elevation_delta((1, 1), (1, 2)), Some(-1));
+        assert_eq!(map.elevation_delta((0, 0), (0, 0)), None);
+        assert_eq!(map.elevation_delta((0, 0), (1, 1)), None);
+        assert_eq!(map.elevation_delta((1, 2), (2, 2)), None);
+        assert_eq!(map.elevation_delta((2, 2), (3, 2)), None);
+    }
+
+    #[test]
+    fn test_shared_map() {
+        let map = SharedBobMap::new(BobMap::from_tiles(grass_tiles(10)));
+
+        let writer = map.clone();
+        let writer = thread::spawn(move || {
+            for x in 0..10 {
+                writer.with_write(|map| map.add_pin(BobPinTypes::I32(x as i32), (x, x)).ok());
+            }
+        });
+        let reader = map.clone();
+        let reader = thread::spawn(move || {
+            // the pins are added one at a time, so the count never goes down
+            let mut last = 0;
+            for _ in 0..100 {
+                let count = reader.with_read(|map| map.iter_pins().len());
+                assert!(count >= last);
+                last = count;
+            }
+        });
+        writer.join().unwrap();
+        reader.join().unwrap();
+
+        assert_eq!(map.read().iter_pins().len(), 10);
+        assert_eq!(*map.read().get_pin((3, 3)).unwrap(), BobPinTypes::I32(3));
+    }
+
+    #[test]
+    fn test_nearest_content() {
+        let mut tiles = grass_tiles(5);
+        tiles[4][4].as_mut().unwrap().content = Content::Rock(1);
+        tiles[0][3].as_mut().unwrap().content = Content::Rock(5);
+        tiles[1][1].as_mut().unwrap().content = Content::Tree(2);
+        tiles[1][0] = None;
+        let map = BobMap::from_tiles(tiles);
+
+        assert_eq!(map.nearest_content((0, 0), &Content::Rock(0)), Some(((0, 3), 3)));
+        assert_eq!(map.nearest_content((3, 4), &Content::Rock(0)), Some(((4, 4), 1)));
+        assert!(map.nearest_content((0, 0), &Content::Fire).is_none());
+    }
+
+    #[test]
+    fn test_bob_type_check_chain() {
+        let mut map = BobMap::from_tiles(grass_tiles(1));
+        let custom = BobPinTypes::Custom(std::sync::Arc::new("landmark".to_string()));
+        map.add_pin(custom, (0, 0)).ok().unwrap();
+
+        let BobPinTypes::Custom(value) = map.get_pin((0, 0)).unwrap().as_ref().clone() else {
+            panic!("expected a custom pin");
+        };
+        let value = bob_type_check::<i32>(value).err().unwrap();
+        let found = bob_type_check::<String>(value).ok().unwrap();
+        assert_eq!(*found, "landmark");
+    }
+
+    #[test]
+    fn test_custom_pin() {
+        let mut map = BobMap::from_tiles(grass_tiles(2));
+        map.add_custom_pin(vec![5.6, 2.1], (0, 1)).ok().unwrap();
+        assert!(map.add_custom_pin(3, (0, 1)).is_err());
+
+        assert_eq!(map.get_custom_pin::<Vec<f64>>((0, 1)).as_deref(), Some(&vec![5.6, 2.1]));
+        assert!(map.get_custom_pin::<i32>((0, 1)).is_none());
+        assert!(map.get_custom_pin::<Vec<f64>>((1, 1)).is_none());
+    }
+
+    #[test]
+    fn test_pin_display() {
+        assert_eq!(BobPinTypes::I32(-4).to_string(), "I32(-4)");
+        assert_eq!(BobPinTypes::String("home".to_string()).to_string(), "String(home)");
+        assert_eq!(BobPinTypes::TileType(TileType::Sand).to_string(), "TileType(Sand)");
+        assert_eq!(BobPinTypes::Contents(Content::Rock(2)).to_string(), "Contents(Rock(2))");
+        assert_eq!(BobPinTypes::City.to_string(), "City");
+        assert_eq!(BobPinTypes::Bank(3).to_string(), "Bank(3)");
+        assert_eq!(BobPinTypes::Market.to_string(), "Market");
+    }
+
+    #[test]
+    fn test_bob_err_display() {
+        let pin = std::sync::Arc::new(BobPinTypes::Bank(3));
+        assert_eq!(BobErr::PinAlreadySet(pin).to_string(), "pin Bank(3) already set at this tile.");
+        assert_eq!(BobErr::PinNotFound.to_string(), "pin not found on the map.");
+        assert_eq!(BobErr::EmptyTile.to_string(), "there are no pins at this tile.");
+        assert_eq!(BobErr::OutOfBounds.to_string(), "coordinates outside the map.");
+
+        let error: Box<dyn std::error::Error> = Box::new(BobErr::EmptyTile);
+        assert_eq!(error.to_string(), "there are no pins at this tile.");
+    }
+
+    #[test]
+    fn test_pin_already_set() {
+        let mut map = BobMap::from_tiles(grass_tiles(2));
+        map.add_pin(BobPinTypes::Market, (0, 1)).ok().unwrap();
+
+        let Err(BobErr::PinAlreadySet(existing)) = map.add_pin(BobPinTypes::City, (0, 1)) else {
+            panic!("expected PinAlreadySet");
+        };
+        assert_eq!(*existing, BobPinTypes::Market);
+        assert_eq!(map.get_pins((0, 1)).len(), 1);
+
+        let old = map.add_or_replace_pin(BobPinTypes::City, (0, 1)).ok().unwrap();
+        assert_eq!(old.len(), 1);
+        assert!(std::sync::Arc::ptr_eq(&old[0], &existing));
+        assert_eq!(*map.get_pin((0, 1)).unwrap(), BobPinTypes::City);
+        assert!(map.search_pin(BobPinTypes::Market).is_err());
+        assert!(map.add_or_replace_pin(BobPinTypes::Market, (1, 1)).ok().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_pins_out_of_bounds() {
+        let mut map = BobMap::from_tiles(grass_tiles(3));
+        map.add_pin(BobPinTypes::Market, (2, 2)).ok().unwrap();
+
+        assert!(matches!(map.add_pin(BobPinTypes::City, (3, 0)), Err(BobErr::OutOfBounds)));
+        assert!(matches!(map.add_pin(BobPinTypes::City, (0, 3)), Err(BobErr::OutOfBounds)));
+        let replaced = map.add_or_replace_pin(BobPinTypes::City, (3, 2));
+        assert!(matches!(replaced, Err(BobErr::OutOfBounds)));
+        let added = map.add_pin_multi(BobPinTypes::City, (1, 3));
+        assert!(matches!(added, Err(BobErr::OutOfBounds)));
+        assert!(map.get_pin((3, 3)).is_none());
+        assert!(map.get_pins((0, 7)).is_empty());
+        assert!(map.get_note((7, 0)).is_none());
+        assert!(matches!(map.delete_pin((5, 1)), Err(BobErr::OutOfBounds)));
+        assert!(matches!(map.delete_pin_matching((1, 5), |_| true), Err(BobErr::OutOfBounds)));
+        assert!(matches!(map.move_pin((2, 2), (2, 3)), Err(BobErr::OutOfBounds)));
+        assert_eq!(map.search_pin(BobPinTypes::Market).ok(), Some(vec![(2, 2)]));
+    }
+
+    #[test]
+    fn test_rectangular_map() {
+        let options = PlannerOptions::energy_save();
+        let grass = grass_tiles(1)[0][0].clone();
+        let mut tiles = vec![vec![grass; 5]; 3];
+        tiles[1][4] = None;
+        let mut map = BobMap::from_tiles(tiles);
+
+        let sand = Tile {
+            tile_type: TileType::Sand,
+            ..grass_tiles(1)[0][0].clone().unwrap()
+        };
+        let update = vec![(1, 4, sand.clone()), (4, 1, sand.clone()), (0, 5, sand)];
+        assert_eq!(map.update_with_diff(update), vec![(1, 4)]);
+        assert_eq!(map.discovered_count(), 15);
+
+        assert_eq!(map.get_tile((2, 4)).map(|tile| &tile.tile_type), Some(&TileType::Grass));
+        assert!(map.get_tile((4, 2)).is_none());
+        map.add_pin(BobPinTypes::Market, (2, 4)).ok().unwrap();
+        assert!(matches!(map.add_pin(BobPinTypes::City, (3, 0)), Err(BobErr::OutOfBounds)));
+        assert_eq!(map.tiles_in_box((0, 3), (9, 9)).len(), 6);
+
+        let path = map.find_path((0, 0), (2, 4), &options);
+        assert_eq!(path.ok().unwrap().0.last(), Some(&(2, 4)));
+    }
+}
@@ -0,0 +1,35 @@
+#[cfg(test)]
+mod tests {
+    use robotics_lib::interface::Direction;
+    use robotics_lib::world::tile::{Content, Tile, TileType};
+
+    use bob_lib::enhanced_map::BobMap;
+    use bob_lib::navigation::estimate_step_cost;
+
+    fn tile(tile_type: TileType) -> Option<Tile> {
+        Some(Tile {
+            tile_type,
+            content: Content::None,
+            elevation: 0,
+        })
+    }
+
+    #[test]
+    fn test_estimate_step_cost() {
+        let map = BobMap::from_tiles(vec![
+            vec![tile(TileType::Street), tile(TileType::Mountain), None],
+            vec![tile(TileType::Grass), tile(TileType::DeepWater), tile(TileType::Wall)],
+        ]);
+
+        assert_eq!(estimate_step_cost(&map, (0, 0), Direction::Right), Some((10, -4)));
+        assert_eq!(estimate_step_cost(&map, (0, 0), Direction::Down), Some((2, 0)));
+        assert_eq!(estimate_step_cost(&map, (1, 0), Direction::Up), Some((1, 0)));
+        assert_eq!(estimate_step_cost(&map, (1, 0), Direction::Right), Some((3, 2)));
+
+        // impassable, undiscovered and outside the map
+        assert_eq!(estimate_step_cost(&map, (1, 1), Direction::Right), None);
+        assert_eq!(estimate_step_cost(&map, (0, 1), Direction::Right), None);
+        assert_eq!(estimate_step_cost(&map, (0, 0), Direction::Up), None);
+        assert_eq!(estimate_step_cost(&map, (1, 0), Direction::Down), None);
+    }
+}
@@ -1,40 +1,12 @@
 #[cfg(test)]
 mod tests {
-    use std::mem::discriminant;
     use std::time::Instant;
 
     use rayon::prelude::*;
     use robotics_lib::world::tile::{Content, Tile, TileType};
 
-    use bob_lib::enhanced_map::BobPinTypes;
-
-    /// Generates a String representation of a grid of tiles.
-    /// Each tile is formatted and printed within a Markdown-like table structure.
-    ///
-    /// # Arguments
-    ///
-    /// * `tiles` - A vector of vectors representing the grid of tiles.
-    ///
-    pub fn pretty_print_tilemap(tiles: Vec<Vec<Tile>>) {
-        for row in tiles {
-            let mut row_str = String::new();
-            for tile in row {
-                let content_display = match tile.content {
-                    Content::None => format!("{:?}", tile.tile_type),
-                    _ => format!("{:?}({})", tile.tile_type, tile.content),
-                };
-                row_str += &format!("| {:<8}\t", content_display); // Adjust the width as needed
-            }
-            println!("{}|", row_str);
-        }
-    }
-
-    pub fn match_content_type_variant(lhs: Option<Content>, rhs: Option<Content>) -> bool {
-        match (lhs, rhs) {
-            (Some(lhs), Some(rhs)) => discriminant(&lhs) == discriminant(&rhs),
-            _ => false,
-        }
-    }
+    use bob_lib::enhanced_map::{BobMap, BobPinTypes};
+    use bob_lib::match_content_type_variant;
 
     pub fn manual_update_testing(robot_map: &mut Vec<Vec<Option<robotics_lib::world::tile::TileType>>>, m: &mut Vec<Vec<(Option<robotics_lib::world::tile::TileType>, Option<BobPinTypes>)>>) {
         m.par_iter_mut().enumerate().for_each(|(i, v)| {
@@ -47,6 +19,37 @@ mod tests {
         })
     }
 
+    #[test]
+    fn test_unchanged_update_speed() {
+        let tile = Tile {
+            tile_type: TileType::Grass,
+            content: Content::Bin(0..5),
+            elevation: 0,
+        };
+        let size = 200;
+        let mut tiles: Vec<(usize, usize, Tile)> = (0..size)
+            .flat_map(|x| (0..size).map(move |y| (x, y)))
+            .map(|(x, y)| (x, y, tile.clone()))
+            .collect();
+        let mut map = BobMap::from_tiles(vec![vec![None; size]; size]);
+
+        println!("time to write every tile: ");
+        let start = Instant::now();
+        let changed = map.update_with_diff(tiles.clone());
+        println!("{:?}", start.elapsed());
+        assert_eq!(changed.len(), size * size);
+
+        // unchanged tiles are only compared, none of them is written again
+        println!("time to compare unchanged tiles: ");
+        let start = Instant::now();
+        let changed = map.update_with_diff(tiles.clone());
+        println!("{:?}", start.elapsed());
+        assert!(changed.is_empty());
+
+        tiles[size + 3].2.tile_type = TileType::Sand;
+        assert_eq!(map.update_with_diff(tiles), vec![(1, 3)]);
+    }
+
     #[test]
     fn test_update_speed() {
         println!("time to create vectors: ");
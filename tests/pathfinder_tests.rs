@@ -0,0 +1,516 @@
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+    use std::collections::HashSet;
+    use std::time::{Duration, Instant};
+
+    use pathfinding::prelude::{astar, dijkstra};
+    use robotics_lib::world::environmental_conditions::WeatherType;
+    use robotics_lib::world::tile::{Content, Tile, TileType};
+
+    use bob_lib::{
+        enhanced_map::BobMap,
+        pathfinder::{
+            find_path, find_path_astar, weather_energy_multiplier, PathCost, PathError,
+            PlannerOptions, NIGHT_END, NIGHT_START,
+        },
+        BobMode,
+        Node,
+    };
+
+    fn tile(tile_type: TileType) -> Option<Tile> {
+        Some(Tile {
+            tile_type,
+            content: Content::None,
+            elevation: 0,
+        })
+    }
+
+    fn grass_map(size: usize) -> Vec<Vec<Option<Tile>>> {
+        vec![vec![tile(TileType::Grass); size]; size]
+    }
+
+    #[test]
+    fn test_successors_corner() {
+        let map = grass_map(3);
+
+        for (x, y) in [(0, 0), (0, 2), (2, 0), (2, 2)] {
+            let successors = Node::new(x, y, 100, 0).successors(&map, &BobMode::EnergySave, 1.0);
+            assert_eq!(successors.len(), 2);
+        }
+    }
+
+    #[test]
+    fn test_successors_center() {
+        let map = grass_map(3);
+        let successors = Node::new(1, 1, 100, 0).successors(&map, &BobMode::EnergySave, 1.0);
+        assert_eq!(successors.len(), 4);
+    }
+
+    #[test]
+    fn test_find_path_energy_budget() {
+        let options = PlannerOptions::energy_save().with_max_energy(6);
+        let map = grass_map(5);
+
+        // every grass tile costs 2 energy
+        assert_eq!(
+            find_path(&map, (0, 0), (0, 4), &options),
+            Err(PathError::NoPath)
+        );
+
+        let (path, cost) = find_path(&map, (0, 0), (0, 3), &options).unwrap();
+        assert_eq!(path, vec![(0, 0), (0, 1), (0, 2), (0, 3)]);
+        assert_eq!(cost.energy, 6);
+
+        // the mountain route weighs less, since it gives rocks, but costs more energy
+        let mut map = vec![vec![tile(TileType::Grass); 4]; 2];
+        map[0][1] = tile(TileType::Mountain);
+        map[1][2] = tile(TileType::ShallowWater);
+        map[1][3] = tile(TileType::Wall);
+        let mountain = vec![(0, 0), (0, 1), (0, 2), (0, 3)];
+        let water = vec![(0, 0), (1, 0), (1, 1), (1, 2), (0, 2), (0, 3)];
+
+        let options = PlannerOptions::energy_save().with_max_energy(100);
+        let (path, cost) = find_path(&map, (0, 0), (0, 3), &options).unwrap();
+        assert_eq!((path, cost.energy), (mountain, 14));
+
+        let options = options.with_max_energy(13);
+        let (path, cost) = find_path(&map, (0, 0), (0, 3), &options).unwrap();
+        assert_eq!((path.clone(), cost.energy), (water, 13));
+        assert_eq!(find_path_astar(&map, (0, 0), (0, 3), &options).unwrap(), (path, cost));
+    }
+
+    #[test]
+    fn test_find_path_errors() {
+        let options = PlannerOptions::energy_save().with_max_energy(100);
+        let mut map = grass_map(3);
+        map[2][2] = tile(TileType::Wall);
+        map[0][1] = tile(TileType::Wall);
+        map[1][0] = tile(TileType::Wall);
+
+        assert_eq!(
+            find_path(&map, (1, 1), (2, 2), &options),
+            Err(PathError::UnreachableGoal)
+        );
+        assert_eq!(
+            find_path(&map, (0, 0), (1, 1), &options),
+            Err(PathError::StartBlocked)
+        );
+        assert_eq!(
+            find_path(&map, (1, 1), (3, 0), &options),
+            Err(PathError::OutOfBounds)
+        );
+    }
+
+    #[test]
+    fn test_find_path_start_blocked_or_unaffordable() {
+        let mut map = grass_map(3);
+        map[0][1] = tile(TileType::Wall);
+        map[1][0] = tile(TileType::Wall);
+
+        // walls can't be entered with any budget
+        let options = PlannerOptions::energy_save().with_max_energy(1_000);
+        assert_eq!(
+            find_path(&map, (0, 0), (2, 2), &options),
+            Err(PathError::StartBlocked)
+        );
+        assert_eq!(
+            find_path_astar(&map, (0, 0), (2, 2), &options),
+            Err(PathError::StartBlocked)
+        );
+
+        // every grass tile costs 2 energy: the neighbours are walkable but unaffordable
+        let options = PlannerOptions::energy_save().with_max_energy(1);
+        assert_eq!(
+            find_path(&map, (1, 1), (2, 2), &options),
+            Err(PathError::NoPath)
+        );
+        assert_eq!(
+            find_path_astar(&map, (1, 1), (2, 2), &options),
+            Err(PathError::NoPath)
+        );
+    }
+
+    #[test]
+    fn test_bob_map_find_path_undiscovered() {
+        let options = PlannerOptions::energy_save().with_max_energy(100);
+        let mut tiles = grass_map(3);
+        tiles[0][1] = None;
+        tiles[1][1] = None;
+        let map = BobMap::from_tiles(tiles);
+
+        // the path has to go around the undiscovered tiles
+        let (path, cost) = map.find_path((0, 0), (0, 2), &options).unwrap();
+        assert_eq!(path.len(), 7);
+        assert_eq!(cost.energy, 12);
+    }
+
+    #[test]
+    fn test_route_waypoints() {
+        let options = PlannerOptions::energy_save().with_max_energy(100);
+        let map = BobMap::from_tiles(grass_map(5));
+        let waypoints = [(0, 4), (4, 4), (4, 0)];
+
+        let (path, cost) = map.route_waypoints((0, 0), &waypoints, &options).unwrap();
+
+        let mut legs_cost = 0;
+        let mut from = (0, 0);
+        for waypoint in waypoints {
+            let (_, leg_cost) = map.find_path(from, waypoint, &options).unwrap();
+            legs_cost += leg_cost.energy;
+            from = waypoint;
+        }
+        assert_eq!(cost.energy, legs_cost);
+        assert_eq!(path.len(), 13);
+        assert_eq!(path.last(), Some(&(4, 0)));
+    }
+
+    #[test]
+    fn test_reachable_tiles() {
+        let mut tiles = vec![vec![tile(TileType::Grass); 5]; 3];
+        // a wall splits the map in two
+        for row in tiles.iter_mut() {
+            row[2] = tile(TileType::Wall);
+        }
+        let map = BobMap::from_tiles(tiles);
+
+        let reachable = map.reachable_tiles((0, 0), 100);
+        let left: HashSet<(usize, usize)> = (0..3).flat_map(|x| [(x, 0), (x, 1)]).collect();
+        assert_eq!(reachable, left);
+
+        // every grass tile costs 2 energy
+        let reachable = map.reachable_tiles((0, 0), 3);
+        assert_eq!(reachable, HashSet::from([(0, 0), (0, 1), (1, 0)]));
+        assert!(map.reachable_tiles((5, 0), 100).is_empty());
+    }
+
+    #[test]
+    fn test_find_path_prefers_flat_terrain() {
+        let options = PlannerOptions::energy_save().with_max_energy(100);
+        let mut map = grass_map(3);
+        // climbing the center tile costs 2 + 3^2 energy
+        map[1][1].as_mut().unwrap().elevation = 3;
+
+        let (path, cost) = find_path(&map, (1, 0), (1, 2), &options).unwrap();
+        assert!(!path.contains(&(1, 1)));
+        assert_eq!(cost.energy, 8);
+    }
+
+    #[test]
+    fn test_find_path_teleport() {
+        let options = PlannerOptions::energy_save().with_max_energy(100);
+        let mut map = vec![vec![tile(TileType::Grass); 25]];
+        map[0][1] = tile(TileType::Teleport(true));
+        map[0][23] = tile(TileType::Teleport(true));
+
+        let (path, cost) = find_path(&map, (0, 0), (0, 24), &options).unwrap();
+        assert_eq!(path, vec![(0, 0), (0, 1), (0, 23), (0, 24)]);
+        assert!(cost.energy < 24 * 2);
+    }
+
+    #[test]
+    fn test_find_path_walled_teleport() {
+        let options = PlannerOptions::energy_save().with_max_energy(100);
+        // the start can only be left through the teleports
+        let mut map = vec![vec![tile(TileType::Grass); 4]];
+        map[0][0] = tile(TileType::Teleport(true));
+        map[0][1] = tile(TileType::Wall);
+        map[0][2] = tile(TileType::Teleport(true));
+
+        let (path, _) = find_path(&map, (0, 0), (0, 3), &options).unwrap();
+        assert_eq!(path, vec![(0, 0), (0, 2), (0, 3)]);
+        let (path, _) = find_path_astar(&map, (0, 0), (0, 3), &options).unwrap();
+        assert_eq!(path, vec![(0, 0), (0, 2), (0, 3)]);
+    }
+
+    #[test]
+    fn test_find_path_astar_speed() {
+        let options = PlannerOptions::energy_save().with_max_energy(10_000);
+        let map = grass_map(200);
+        let (start, goal) = ((0, 0), (150, 120));
+
+        println!("time to find path with dijkstra: ");
+        let now = Instant::now();
+        let (_, dijkstra_cost) = find_path(&map, start, goal, &options).unwrap();
+        println!("{:?}", now.elapsed());
+
+        println!("time to find path with astar: ");
+        let now = Instant::now();
+        let (_, astar_cost) = find_path_astar(&map, start, goal, &options).unwrap();
+        println!("{:?}", now.elapsed());
+
+        assert_eq!(dijkstra_cost, astar_cost);
+
+        // count the expanded nodes with the same successors and heuristic
+        let expanded = Cell::new(0);
+        let start_node = Node::new(start.0, start.1, 10_000, 0);
+        let successors = |node: &Node| {
+            expanded.set(expanded.get() + 1);
+            node.successors(&map, &BobMode::EnergySave, 1.0)
+        };
+        let success = |node: &Node| (node.x, node.y) == goal;
+
+        dijkstra(&start_node, successors, success);
+        let dijkstra_expanded = expanded.replace(0);
+        astar(
+            &start_node,
+            successors,
+            |node| (node.x.abs_diff(goal.0) + node.y.abs_diff(goal.1)) * 2,
+            success,
+        );
+        println!("expanded nodes: dijkstra {}, astar {}", dijkstra_expanded, expanded.get());
+        assert!(expanded.get() < dijkstra_expanded);
+    }
+
+    #[test]
+    fn test_find_path_large_map_speed() {
+        // 3x3 blocks of grass, hills, mountains and deep water, so the tiles are reached
+        // with many different energy and rocks
+        let kinds = [TileType::Grass, TileType::Hill, TileType::Mountain, TileType::DeepWater];
+        let map: Vec<Vec<Option<Tile>>> = (0..100)
+            .map(|x| (0..100).map(|y| tile(kinds[(x / 3 + y / 3) % 4].clone())).collect())
+            .collect();
+        let options = PlannerOptions::energy_save()
+            .with_max_energy(1_000_000)
+            .with_rocks(20);
+        let (start, goal) = ((0, 0), (99, 99));
+
+        let now = Instant::now();
+        let (_, dijkstra_cost) = find_path(&map, start, goal, &options).unwrap();
+        let (_, astar_cost) = find_path_astar(&map, start, goal, &options).unwrap();
+        let reachable = BobMap::from_tiles(map).reachable_tiles(start, 1_000_000);
+        println!("time to search the large map: {:?}", now.elapsed());
+
+        assert_eq!(dijkstra_cost, astar_cost);
+        assert!(reachable.contains(&goal));
+        // the paths kept for every tile don't grow with the energy budget
+        assert!(now.elapsed() < Duration::from_secs(10));
+    }
+
+    #[test]
+    fn test_find_path_weighted() {
+        let options = PlannerOptions::energy_save().with_max_energy(100);
+        let mut map = grass_map(3);
+        // crossing deep water is shorter but costs rocks
+        map[0][1] = tile(TileType::DeepWater);
+
+        let energy_only = BobMode::Weighted {
+            energy_weight: 1.0,
+            material_weight: 0.0,
+        };
+        let material_only = BobMode::Weighted {
+            energy_weight: 0.0,
+            material_weight: 1.0,
+        };
+
+        let options = options.with_rocks(20);
+        let energy_options = options.clone().with_mode(energy_only);
+        let (energy_path, _) = find_path(&map, (0, 0), (0, 2), &energy_options).unwrap();
+        let material_options = options.with_mode(material_only);
+        let (material_path, material_cost) =
+            find_path(&map, (0, 0), (0, 2), &material_options).unwrap();
+
+        assert_ne!(energy_path, material_path);
+        assert!(energy_path.contains(&(0, 1)));
+        assert!(!material_path.contains(&(0, 1)));
+        assert_eq!(material_cost.material, 0);
+    }
+
+    #[test]
+    fn test_rock_capacity() {
+        let options = PlannerOptions::energy_save().with_max_energy(100);
+        let mut map = vec![vec![tile(TileType::Grass); 3]; 2];
+        // crossing the mountain gives 4 rocks, the deep water costs 2
+        map[0][1] = tile(TileType::Mountain);
+        map[1][1] = tile(TileType::DeepWater);
+
+        let rocks_after_mountain = |rocks: isize| {
+            Node::new(0, 0, 100, rocks)
+                .with_capacity(20)
+                .successors(&map, &BobMode::EnergySave, 1.0)
+                .into_iter()
+                .find(|(node, _)| (node.x, node.y) == (0, 1))
+                .map(|(node, _)| node.rocks)
+        };
+        assert_eq!(rocks_after_mountain(3), Some(7));
+        assert_eq!(rocks_after_mountain(18), Some(20));
+
+        // without rocks the deep water can't be crossed
+        let from_below = |rocks: isize| {
+            Node::new(1, 0, 100, rocks)
+                .successors(&map, &BobMode::EnergySave, 1.0)
+                .into_iter()
+                .any(|(node, _)| (node.x, node.y) == (1, 1))
+        };
+        assert!(!from_below(1));
+        assert!(from_below(2));
+
+        let options = options.with_mode(BobMode::AllOut);
+        let (path, _) = find_path(&map, (1, 0), (1, 2), &options).unwrap();
+        assert!(!path.contains(&(1, 1)));
+    }
+
+    #[test]
+    fn test_find_path_collects_rocks() {
+        let options = PlannerOptions::energy_save().with_max_energy(100);
+        let mut map = vec![vec![tile(TileType::Grass); 5]; 2];
+        // the deep water costs 2 rocks: the cheapest route reaches it without any, the
+        // other one crosses the mountain first
+        map[0][3] = tile(TileType::DeepWater);
+        map[1][1] = tile(TileType::Mountain);
+        map[1][3] = tile(TileType::Wall);
+        map[1][4] = tile(TileType::Wall);
+
+        let (path, cost) = find_path(&map, (0, 0), (0, 4), &options).unwrap();
+        assert!(path.contains(&(1, 1)));
+        assert_eq!(cost.material, -2);
+        let (_, astar_cost) = find_path_astar(&map, (0, 0), (0, 4), &options).unwrap();
+        assert_eq!(astar_cost, cost);
+
+        // a backpack holding a single rock can't carry enough of them
+        let options = options.with_rock_capacity(1);
+        assert_eq!(
+            find_path(&map, (0, 0), (0, 4), &options),
+            Err(PathError::NoPath)
+        );
+    }
+
+    #[test]
+    fn test_path_error_display() {
+        assert_eq!(PathError::UnreachableGoal.to_string(), "the goal can't be reached.");
+        assert_eq!(PathError::StartBlocked.to_string(), "the start is blocked.");
+        assert_eq!(
+            PathError::OutOfBounds.to_string(),
+            "the start or the goal are outside the map."
+        );
+        assert_eq!(PathError::NoPath.to_string(), "no path found within the energy budget.");
+    }
+
+    #[test]
+    fn test_find_path_weather() {
+        let options = PlannerOptions::energy_save().with_max_energy(100);
+        let map = grass_map(4);
+
+        let sunny = options.clone().with_weather(WeatherType::Sunny);
+        let (sunny_path, sunny_cost) = find_path(&map, (0, 0), (3, 3), &sunny).unwrap();
+        let (_, no_weather_cost) = find_path(&map, (0, 0), (3, 3), &options).unwrap();
+        let rainy = options.with_weather(WeatherType::Rainy);
+        let (rainy_path, rainy_cost) = find_path(&map, (0, 0), (3, 3), &rainy).unwrap();
+
+        assert_eq!(sunny_cost, no_weather_cost);
+        assert_eq!(sunny_path.len(), rainy_path.len());
+        assert!(rainy_cost.energy > sunny_cost.energy);
+        assert!(weather_energy_multiplier(&WeatherType::TropicalMonsoon) > 1.0);
+    }
+
+    #[test]
+    fn test_find_path_night() {
+        let map = grass_map(4);
+        let options = PlannerOptions::energy_save().with_night_multiplier(3.0);
+
+        let day = options.clone().with_hour(12);
+        let night = options.with_hour(NIGHT_START);
+        assert!(!day.is_night());
+        assert!(night.is_night());
+        assert!(night.clone().with_hour(NIGHT_END - 1).is_night());
+        assert!(!night.clone().with_hour(NIGHT_END).is_night());
+
+        let (_, day_cost) = find_path(&map, (0, 0), (3, 3), &day).unwrap();
+        let (_, night_cost) = find_path(&map, (0, 0), (3, 3), &night).unwrap();
+        assert_eq!(night_cost.energy, day_cost.energy * 3);
+    }
+
+    #[test]
+    fn test_find_path_diagonal() {
+        let options = PlannerOptions::energy_save().with_max_energy(100);
+        let map = grass_map(3);
+
+        let (straight_path, straight_cost) = find_path(&map, (0, 0), (2, 2), &options).unwrap();
+        let diagonal = options.with_allow_diagonal(true);
+        let (path, cost) = find_path(&map, (0, 0), (2, 2), &diagonal).unwrap();
+        let (_, astar_cost) = find_path_astar(&map, (0, 0), (2, 2), &diagonal).unwrap();
+
+        // a diagonal grass step costs the ceiling of 2√2
+        assert_eq!(path, vec![(0, 0), (1, 1), (2, 2)]);
+        assert_eq!((straight_path.len(), straight_cost.energy), (5, 8));
+        assert_eq!(cost.energy, 6);
+        assert_eq!(astar_cost, cost);
+
+        let successors = Node::new(0, 0, 100, 0)
+            .with_diagonal(true)
+            .successors(&map, &BobMode::EnergySave, 1.0);
+        assert_eq!(successors.len(), 3);
+    }
+
+    #[test]
+    fn test_find_path_material() {
+        let options = PlannerOptions::energy_save().with_max_energy(100).with_rocks(5);
+        let mut map = vec![vec![tile(TileType::Wall); 4]; 2];
+        // a street through a mountain, then deep water which needs a street built on it
+        map[0][0] = tile(TileType::Street);
+        map[0][1] = tile(TileType::Mountain);
+        map[0][2] = tile(TileType::Street);
+        map[0][3] = tile(TileType::DeepWater);
+
+        let (path, cost) = find_path(&map, (0, 0), (0, 2), &options).unwrap();
+        assert_eq!(path, vec![(0, 0), (0, 1), (0, 2)]);
+        assert_eq!(cost, PathCost { energy: 11, material: -4 });
+
+        let (_, cost) = find_path(&map, (0, 0), (0, 3), &options).unwrap();
+        assert_eq!(cost, PathCost { energy: 14, material: -2 });
+
+        // the rocks gained can't overflow the backpack
+        let full = options.with_rocks(20);
+        let (_, cost) = find_path(&map, (0, 0), (0, 2), &full).unwrap();
+        assert_eq!(cost.material, 0);
+    }
+
+    #[test]
+    fn test_find_path_avoid_contents() {
+        let options = PlannerOptions::energy_save().with_max_energy(100);
+        let mut map = grass_map(3);
+        map[0][1].as_mut().unwrap().content = Content::Fire;
+
+        let (path, _) = find_path(&map, (0, 0), (0, 2), &options).unwrap();
+        assert_eq!(path, vec![(0, 0), (0, 1), (0, 2)]);
+
+        let fragile = options.with_avoid_contents(vec![Content::Fire]);
+        let (path, cost) = find_path(&map, (0, 0), (0, 2), &fragile).unwrap();
+        assert!(!path.contains(&(0, 1)));
+        assert_eq!(path.len(), 5);
+        assert_eq!(cost.energy, 8);
+    }
+
+    #[test]
+    fn test_find_path_prefer_contents() {
+        let options = PlannerOptions::energy_save().with_max_energy(100);
+        let mut map = grass_map(3);
+        // the direct path crosses shallow water, the detour passes by a coin
+        map[0][1] = tile(TileType::ShallowWater);
+        map[1][1].as_mut().unwrap().content = Content::Coin(1);
+
+        let (path, cost) = find_path(&map, (0, 0), (0, 2), &options).unwrap();
+        assert_eq!(path, vec![(0, 0), (0, 1), (0, 2)]);
+        assert_eq!(cost.energy, 7);
+
+        let greedy = options.with_prefer_contents(vec![Content::Coin(0)], 2);
+        let (path, cost) = find_path(&map, (0, 0), (0, 2), &greedy).unwrap();
+        assert_eq!(path, vec![(0, 0), (1, 0), (1, 1), (1, 2), (0, 2)]);
+        // the energy really spent is not lowered
+        assert_eq!(cost.energy, 8);
+        let (astar_path, _) = find_path_astar(&map, (0, 0), (0, 2), &greedy).unwrap();
+        assert_eq!(astar_path, path);
+    }
+
+    #[test]
+    fn test_planner_options() {
+        let options = PlannerOptions::energy_save();
+        assert_eq!(options, PlannerOptions::default());
+        assert_eq!(options.mode, BobMode::EnergySave);
+        assert_eq!(options.rock_capacity, 20);
+
+        let options = options.with_mode(BobMode::AllOut).with_max_energy(7).with_rocks(3);
+        assert_eq!((options.mode, options.max_energy, options.rocks), (BobMode::AllOut, 7, 3));
+        assert_eq!(options.energy_multiplier(), 1.0);
+    }
+}
@@ -0,0 +1,49 @@
+#[cfg(test)]
+mod tests {
+    use robotics_lib::world::tile::{Content, Tile, TileType};
+
+    use bob_lib::enhanced_map::{BobMap, BobPinTypes};
+    use bob_lib::render::{content_symbol, render_bobmap, render_dense, tiletype_symbol};
+
+    fn map() -> BobMap {
+        let tile = Tile {
+            tile_type: TileType::Grass,
+            content: Content::None,
+            elevation: 0,
+        };
+        let mut tiles = vec![vec![Some(tile); 4]; 3];
+        tiles[2][3] = None;
+        BobMap::from_tiles(tiles)
+    }
+
+    #[test]
+    fn test_render_bobmap() {
+        let mut map = map();
+        map.add_pin(BobPinTypes::Market, (0, 1)).ok().unwrap();
+
+        let rendered = render_bobmap(&map, Some((1, 2)), false);
+        let rows: Vec<&str> = rendered.lines().collect();
+        assert_eq!(rows.len(), 3);
+        assert_eq!(rows[0], ",  , *,  ,  ");
+        assert_eq!(rows[1], ",  ,  @  ,  ");
+        assert_eq!(rows[2], ",  ,  ,  ?  ");
+
+        let colored = render_bobmap(&map, Some((1, 2)), true);
+        assert_eq!(colored.lines().count(), 3);
+        assert!(colored.contains("\x1b[42m"));
+    }
+
+    #[test]
+    fn test_symbols() {
+        assert_eq!(tiletype_symbol(&TileType::DeepWater), '~');
+        assert_eq!(tiletype_symbol(&TileType::Teleport(true)), '&');
+        assert_eq!(content_symbol(&Content::Fire), 'F');
+        assert_eq!(content_symbol(&Content::Rock(3)), 'R');
+        assert_eq!(content_symbol(&Content::None), ' ');
+
+        let mut map = map();
+        map.get_tile_mut((0, 0)).unwrap().content = Content::Tree(2);
+        let rendered = render_dense(&map, Some((1, 1)));
+        assert_eq!(rendered, "T,,,\n,@,,\n,,,?\n");
+    }
+}
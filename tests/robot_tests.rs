@@ -0,0 +1,78 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    use robotics_lib::event::events::Event;
+    use robotics_lib::interface::Direction;
+    use robotics_lib::runner::{Robot, Runnable};
+    use robotics_lib::world::tile::{Content, TileType};
+
+    use bob_lib::robot::BobRobot;
+    use bob_lib::tracker::{destroy_and_collect_item, Goal, GoalTracker, GoalType};
+
+    use crate::common::{run_ticks, TestWorld};
+
+    fn rocks_goal(quantity: u32) -> Goal {
+        Goal::new(
+            "rocks".to_string(),
+            String::new(),
+            GoalType::GetItems,
+            Some(Content::Rock(0)),
+            quantity,
+        )
+    }
+
+    #[test]
+    fn test_bob_robot() {
+        let mut goal_tracker = GoalTracker::new();
+        goal_tracker.add_goal(rocks_goal(2));
+        let mut robot = BobRobot::with_goal_tracker(Box::new(|_, _, _, _| {}), goal_tracker);
+
+        // the getters are the ones of a new robot
+        let inner = Robot::new();
+        assert_eq!(
+            robot.get_energy().get_energy_level(),
+            inner.energy.get_energy_level()
+        );
+        assert_eq!(robot.get_coordinate().get_row(), inner.coordinate.get_row());
+        assert_eq!(robot.map().dimensions(), (0, 0));
+
+        // the goals are moved by the actions of the tracker only
+        robot.handle_event(Event::AddedToBackpack(Content::Rock(0), 2));
+        assert!(!robot.goal_tracker().all_completed());
+    }
+
+    #[test]
+    fn test_bob_robot_collect_once() {
+        let world = TestWorld::new(3, (1, 1)).with_tile((1, 2), TileType::Grass, Content::Rock(2));
+        // the rocks collected and the goal left, read at the tick after the collect
+        let checked = Rc::new(RefCell::new(None));
+        let done = Rc::clone(&checked);
+        let mut tick = 0;
+
+        run_ticks(world, 2, move |body, _, goal_tracker, world| {
+            if tick == 0 {
+                goal_tracker.add_goal(rocks_goal(10));
+                let progress = destroy_and_collect_item(
+                    body,
+                    world,
+                    Direction::Right,
+                    goal_tracker,
+                    Some(Content::Rock(0)),
+                )
+                .unwrap();
+                assert_eq!(progress.delta as usize, 2);
+            } else {
+                let backpack = body.get_backpack().get_contents();
+                let collected = backpack.get(&Content::Rock(0)).copied().unwrap_or(0);
+                let left = *goal_tracker.get_goals()[0].get_items_left();
+                *done.borrow_mut() = Some((collected, left));
+            }
+            tick += 1;
+        });
+        assert_eq!(*checked.borrow(), Some((2, 8)));
+    }
+}
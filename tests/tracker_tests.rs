@@ -0,0 +1,535 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    use robotics_lib::event::events::Event;
+    use robotics_lib::interface::{destroy, Direction};
+    use robotics_lib::runner::Runnable;
+    use robotics_lib::utils::LibError;
+    use robotics_lib::world::tile::{Content, Tile, TileType};
+
+    use bob_lib::enhanced_map::BobMap;
+    use bob_lib::tracker::{
+        put_out_fire_with, sell_items_in_market, ActionError, Goal, GoalError, GoalProgress,
+        GoalTracker, GoalType,
+    };
+
+    use crate::common::{run_tick, TestWorld};
+
+    fn goal(name: &str, goal_type: GoalType, item_type: Option<Content>, quantity: u32) -> Goal {
+        Goal::new(name.to_string(), String::new(), goal_type, item_type, quantity)
+    }
+
+    #[test]
+    fn test_progress() {
+        let mut tracker = GoalTracker::new();
+        tracker.add_goal(goal("rocks", GoalType::GetItems, Some(Content::Rock(0)), 4));
+        tracker.add_goal(goal("fire", GoalType::PutOutFire, Some(Content::Water(0)), 2));
+        tracker.add_goal(goal("nothing", GoalType::SellItems, Some(Content::Fish(0)), 0));
+
+        tracker.update_manual(GoalType::GetItems, Some(Content::Rock(0)), 1).unwrap();
+
+        let progress: Vec<f32> = tracker.get_goals().iter().map(Goal::progress).collect();
+        assert_eq!(progress, vec![0.25, 0.0, 1.0]);
+        assert_eq!(tracker.overall_progress(), 1.25 / 3.0);
+
+        // removing more items than needed doesn't go over 1.0
+        tracker.update_manual(GoalType::GetItems, Some(Content::Rock(0)), 10).unwrap();
+        assert_eq!(tracker.get_goals()[0].progress(), 1.0);
+    }
+
+    #[test]
+    fn test_deadline() {
+        let mut tracker = GoalTracker::new();
+        let rocks = goal("rocks", GoalType::GetItems, Some(Content::Rock(0)), 2);
+        tracker.add_goal(rocks.with_deadline(2));
+        let fire = goal("fire", GoalType::PutOutFire, Some(Content::Water(0)), 1);
+        tracker.add_goal(fire.with_deadline(2));
+        tracker.add_goal(goal("fish", GoalType::GetItems, Some(Content::Fish(0)), 1));
+
+        tracker.tick();
+        tracker.update_manual(GoalType::PutOutFire, Some(Content::Water(0)), 1).unwrap();
+        assert!(tracker.get_expired().is_empty());
+
+        tracker.tick();
+        tracker.tick();
+        let expired = tracker.get_expired();
+        assert_eq!(expired.len(), 1);
+        assert_eq!(expired[0].get_name(), "rocks");
+        assert!(!tracker.get_goals()[1].is_expired());
+        assert!(!tracker.get_goals()[2].is_expired());
+
+        // expired goals are not updated anymore
+        let result = tracker.update_manual(GoalType::GetItems, Some(Content::Rock(0)), 2);
+        assert_eq!(
+            result,
+            Err(GoalError::NotFound {
+                goal_type: GoalType::GetItems,
+                item_type: Some(Content::Rock(0)),
+            })
+        );
+        assert_eq!(*tracker.get_goals()[0].get_items_left(), 2);
+    }
+
+    #[test]
+    fn test_goals_by_priority() {
+        let mut tracker = GoalTracker::new();
+        tracker.add_goal(goal("first", GoalType::GetItems, Some(Content::Rock(0)), 1));
+        let urgent = goal("urgent", GoalType::PutOutFire, Some(Content::Water(0)), 1);
+        tracker.add_goal(urgent.with_priority(200));
+        tracker.add_goal(goal("second", GoalType::SellItems, Some(Content::Fish(0)), 1));
+        let done = goal("done", GoalType::ThrowGarbage, Some(Content::Garbage(0)), 1);
+        tracker.add_goal(done.with_priority(255));
+        let later = goal("later", GoalType::GetItems, Some(Content::Fish(0)), 1);
+        tracker.add_goal(later.with_priority(3));
+
+        tracker.update_manual(GoalType::ThrowGarbage, Some(Content::Garbage(0)), 1).unwrap();
+
+        let names: Vec<&str> = tracker
+            .goals_by_priority()
+            .iter()
+            .map(|goal| goal.get_name().as_str())
+            .collect();
+        assert_eq!(names, vec!["urgent", "first", "second", "later"]);
+    }
+
+    #[test]
+    fn test_on_complete() {
+        let mut tracker = GoalTracker::new();
+        tracker.add_goal(goal("fire", GoalType::PutOutFire, Some(Content::Water(0)), 2));
+        tracker.add_goal(goal("garbage", GoalType::ThrowGarbage, Some(Content::Garbage(0)), 1));
+
+        let calls = Rc::new(RefCell::new(vec![]));
+        for callback in ["first", "second"] {
+            let calls = calls.clone();
+            tracker.on_complete(Box::new(move |goal| {
+                calls.borrow_mut().push(format!("{} {}", callback, goal.get_name()));
+            }));
+        }
+
+        tracker.update_manual(GoalType::PutOutFire, Some(Content::Water(0)), 1).unwrap();
+        assert!(calls.borrow().is_empty());
+
+        tracker.update_manual(GoalType::ThrowGarbage, Some(Content::Garbage(0)), 1).unwrap();
+        tracker.update_manual(GoalType::PutOutFire, Some(Content::Water(0)), 1).unwrap();
+        assert_eq!(
+            *calls.borrow(),
+            vec!["first garbage", "second garbage", "first fire", "second fire"]
+        );
+    }
+
+    #[test]
+    fn test_composite_goal() {
+        let mut tracker = GoalTracker::new();
+        tracker.add_goal(goal("rocks", GoalType::GetItems, Some(Content::Rock(0)), 2));
+        tracker.add_goal(goal("trees", GoalType::GetItems, Some(Content::Tree(0)), 1));
+        let children = vec!["rocks".to_string(), "trees".to_string()];
+        tracker.add_goal(Goal::composite("build".to_string(), String::new(), children));
+
+        tracker.update_manual(GoalType::GetItems, Some(Content::Rock(0)), 2).unwrap();
+        assert!(!tracker.is_composite_complete("build"));
+        assert_eq!(*tracker.get_goals()[2].get_items_left(), 1);
+
+        tracker.update_manual(GoalType::GetItems, Some(Content::Tree(0)), 1).unwrap();
+        assert!(tracker.is_composite_complete("build"));
+        assert_eq!(tracker.get_completed_number(), 3);
+        assert!(!tracker.is_composite_complete("rocks"));
+    }
+
+    #[test]
+    fn test_remove_more_than_needed() {
+        let mut tracker = GoalTracker::new();
+        tracker.add_goal(goal("rocks", GoalType::GetItems, Some(Content::Rock(0)), 3));
+
+        // e.g. destroying a Rock(5)
+        tracker.update_manual(GoalType::GetItems, Some(Content::Rock(0)), 5).unwrap();
+
+        let goal = &tracker.get_goals()[0];
+        assert_eq!(*goal.get_items_left(), 0);
+        assert!(*goal.get_completed());
+        assert_eq!(tracker.get_completed_number(), 1);
+    }
+
+    #[test]
+    fn test_completed_counted_once() {
+        let mut tracker = GoalTracker::new();
+        tracker.add_goal(goal("rocks", GoalType::GetItems, Some(Content::Rock(0)), 1));
+
+        tracker.update_manual(GoalType::GetItems, Some(Content::Rock(0)), 1).unwrap();
+        tracker.update_manual(GoalType::GetItems, Some(Content::Rock(0)), 1).unwrap();
+        assert_eq!(tracker.get_completed_number(), 1);
+    }
+
+    #[test]
+    fn test_match_value() {
+        let mut tracker = GoalTracker::new();
+        let exact = goal("exact", GoalType::GetItems, Some(Content::Rock(3)), 6);
+        tracker.add_goal(exact.with_match_value(true));
+        tracker.add_goal(goal("any", GoalType::GetItems, Some(Content::Rock(3)), 6));
+
+        // Rock(2) only matches the variant, so the second goal is updated
+        tracker.update_manual(GoalType::GetItems, Some(Content::Rock(2)), 2).unwrap();
+        assert_eq!(*tracker.get_goals()[0].get_items_left(), 6);
+        assert_eq!(*tracker.get_goals()[1].get_items_left(), 4);
+
+        tracker.update_manual(GoalType::GetItems, Some(Content::Rock(3)), 3).unwrap();
+        assert_eq!(*tracker.get_goals()[0].get_items_left(), 3);
+        assert_eq!(*tracker.get_goals()[1].get_items_left(), 4);
+    }
+
+    #[test]
+    fn test_get_goal() {
+        let mut tracker = GoalTracker::new();
+        tracker.add_goal(goal("rocks", GoalType::GetItems, Some(Content::Rock(0)), 3));
+        tracker.add_goal(goal("fish", GoalType::GetItems, Some(Content::Fish(0)), 2));
+
+        assert_eq!(*tracker.get_goal("fish").unwrap().get_goal_quantity(), 2);
+        assert!(tracker.get_goal("trees").is_none());
+
+        tracker.get_goal_mut("rocks").unwrap().description = "for the bridge".to_string();
+        assert_eq!(tracker.get_goal("rocks").unwrap().get_description(), "for the bridge");
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_json_round_trip() {
+        let mut tracker = GoalTracker::new();
+        tracker.add_goal(goal("rocks", GoalType::GetItems, Some(Content::Rock(0)), 5));
+        tracker.add_goal(goal("fire", GoalType::PutOutFire, Some(Content::Water(0)), 3));
+        tracker.add_goal(goal("garbage", GoalType::ThrowGarbage, Some(Content::Garbage(0)), 1));
+        tracker.update_manual(GoalType::GetItems, Some(Content::Rock(0)), 2).unwrap();
+        tracker.update_manual(GoalType::PutOutFire, Some(Content::Water(0)), 1).unwrap();
+        tracker.update_manual(GoalType::ThrowGarbage, Some(Content::Garbage(0)), 1).unwrap();
+
+        let restored = GoalTracker::from_json(&tracker.to_json().unwrap()).unwrap();
+
+        assert_eq!(restored, tracker);
+        let items_left: Vec<u32> =
+            restored.get_goals().iter().map(|goal| goal.items_left).collect();
+        assert_eq!(items_left, vec![3, 2, 0]);
+    }
+
+    #[test]
+    fn test_reset_all() {
+        let mut tracker = GoalTracker::new();
+        tracker.add_goal(goal("rocks", GoalType::GetItems, Some(Content::Rock(0)), 2));
+        let fire = goal("fire", GoalType::PutOutFire, Some(Content::Water(0)), 1);
+        tracker.add_goal(fire.with_deadline(1));
+
+        tracker.update_manual(GoalType::GetItems, Some(Content::Rock(0)), 2).unwrap();
+        tracker.tick();
+        assert_eq!(tracker.get_completed_number(), 1);
+        assert_eq!(tracker.get_expired().len(), 1);
+
+        tracker.reset_all();
+        assert_eq!(tracker.get_completed_number(), 0);
+        assert!(tracker.get_expired().is_empty());
+        assert_eq!(*tracker.get_goal("rocks").unwrap().get_items_left(), 2);
+        assert_eq!(*tracker.get_goal("fire").unwrap().get_ticks_left(), Some(1));
+
+        // the goals can be completed again
+        tracker.update_manual(GoalType::GetItems, Some(Content::Rock(0)), 2).unwrap();
+        assert_eq!(tracker.get_completed_number(), 1);
+    }
+
+    #[test]
+    fn test_merge() {
+        let mut tracker = GoalTracker::new();
+        tracker.add_goal(goal("rocks", GoalType::GetItems, Some(Content::Rock(0)), 4));
+        tracker.add_goal(goal("fire", GoalType::PutOutFire, Some(Content::Water(0)), 1));
+        tracker.update_manual(GoalType::PutOutFire, Some(Content::Water(0)), 1).unwrap();
+
+        let mut other = GoalTracker::new();
+        other.add_goal(goal("rocks", GoalType::GetItems, Some(Content::Rock(0)), 4));
+        other.add_goal(goal("fire", GoalType::PutOutFire, Some(Content::Water(0)), 1));
+        other.add_goal(goal("fish", GoalType::SellItems, Some(Content::Fish(0)), 2));
+        other.update_manual(GoalType::GetItems, Some(Content::Rock(0)), 4).unwrap();
+
+        let conflicts = tracker.merge(other);
+        assert_eq!(conflicts, vec!["rocks".to_string(), "fire".to_string()]);
+        assert_eq!(tracker.get_goals().len(), 3);
+        // the more progressed goal is kept in both cases
+        assert!(*tracker.get_goal("rocks").unwrap().get_completed());
+        assert!(*tracker.get_goal("fire").unwrap().get_completed());
+        assert_eq!(tracker.get_completed_number(), 2);
+    }
+
+    #[test]
+    fn test_all_completed() {
+        let mut tracker = GoalTracker::new();
+        assert!(!tracker.all_completed());
+        assert!(tracker.incomplete_goals().is_empty());
+
+        tracker.add_goal(goal("rocks", GoalType::GetItems, Some(Content::Rock(0)), 2));
+        tracker.add_goal(goal("fire", GoalType::PutOutFire, Some(Content::Water(0)), 1));
+        tracker.update_manual(GoalType::PutOutFire, Some(Content::Water(0)), 1).unwrap();
+        assert!(!tracker.all_completed());
+        let incomplete: Vec<&String> =
+            tracker.incomplete_goals().into_iter().map(Goal::get_name).collect();
+        assert_eq!(incomplete, vec!["rocks"]);
+
+        tracker.update_manual(GoalType::GetItems, Some(Content::Rock(0)), 2).unwrap();
+        assert!(tracker.all_completed());
+        assert!(tracker.incomplete_goals().is_empty());
+    }
+
+    #[test]
+    fn test_zero_quantity_goal() {
+        let nothing = goal("nothing", GoalType::SellItems, Some(Content::Fish(0)), 0);
+        assert!(*nothing.get_completed());
+        assert_eq!(*nothing.get_items_left(), 0);
+
+        let mut tracker = GoalTracker::new();
+        tracker.add_goal(nothing);
+        assert_eq!(tracker.get_completed_number(), 1);
+        assert!(tracker.all_completed());
+
+        // resetting it doesn't make it incomplete
+        tracker.reset_all();
+        assert_eq!(tracker.get_completed_number(), 1);
+    }
+
+    #[test]
+    fn test_goal_builder() {
+        let built = Goal::builder()
+            .name("rocks")
+            .description("collect some rocks")
+            .goal_type(GoalType::GetItems)
+            .item(Content::Rock(0))
+            .quantity(5)
+            .priority(200)
+            .deadline(10)
+            .build()
+            .unwrap();
+        assert_eq!(built.get_name(), "rocks");
+        assert_eq!(built.get_description(), "collect some rocks");
+        assert_eq!(built.item_type, Some(Content::Rock(0)));
+        assert_eq!((*built.get_items_left(), *built.get_priority()), (5, 200));
+        assert_eq!(*built.get_ticks_left(), Some(10));
+
+        let defaults = Goal::builder().name("fire").goal_type(GoalType::PutOutFire).build();
+        assert_eq!(*defaults.unwrap().get_goal_quantity(), 1);
+
+        let no_type = Goal::builder().name("fire").build();
+        assert_eq!(no_type.err(), Some(GoalError::MissingField("goal type")));
+        let no_name = Goal::builder().goal_type(GoalType::PutOutFire).build();
+        assert_eq!(no_name.err(), Some(GoalError::MissingField("name")));
+    }
+
+    #[test]
+    fn test_update_from_event() {
+        let mut tracker = GoalTracker::new();
+        tracker.add_goal(goal("rocks", GoalType::GetItems, Some(Content::Rock(0)), 5));
+
+        let progress = tracker.update_from_event(&Event::AddedToBackpack(Content::Rock(0), 3));
+        assert_eq!(progress.unwrap().unwrap().items_left, 2);
+        let progress = tracker.update_from_event(&Event::AddedToBackpack(Content::Tree(0), 1));
+        assert!(matches!(progress, Some(Err(GoalError::NotFound { .. }))));
+
+        // removing items doesn't tell which goal they complete
+        let progress = tracker.update_from_event(&Event::RemovedFromBackpack(Content::Rock(0), 2));
+        assert!(progress.is_none());
+        assert!(tracker.update_from_event(&Event::Ready).is_none());
+
+        tracker.update_from_event(&Event::AddedToBackpack(Content::Rock(0), 2));
+        assert!(tracker.all_completed());
+    }
+
+    #[test]
+    fn test_put_items_goal() {
+        let mut tracker = GoalTracker::new();
+        tracker.add_goal(goal("street", GoalType::PutItems, Some(Content::Rock(0)), 3));
+        tracker.add_goal(goal("rocks", GoalType::GetItems, Some(Content::Rock(0)), 3));
+
+        // e.g. put_and_track placing 2 rocks
+        let progress = tracker.update_manual(GoalType::PutItems, Some(Content::Rock(0)), 2);
+        assert_eq!(progress.unwrap().goal_name, "street");
+        assert_eq!(*tracker.get_goal("rocks").unwrap().get_items_left(), 3);
+
+        let progress = tracker.update_manual(GoalType::PutItems, Some(Content::Rock(0)), 1);
+        assert!(progress.unwrap().just_completed);
+    }
+
+    #[test]
+    fn test_estimated_value() {
+        let mut tracker = GoalTracker::new();
+        assert_eq!(tracker.estimated_value(|_| 1.0), 0.0);
+
+        tracker.add_goal(goal("rocks", GoalType::GetItems, Some(Content::Rock(0)), 4));
+        tracker.add_goal(goal("coins", GoalType::GetItems, Some(Content::Coin(0)), 2));
+        tracker.add_goal(goal("fish", GoalType::SellItems, Some(Content::Fish(0)), 1));
+        tracker.add_goal(goal("explore", GoalType::Explore, None, 10));
+        let value = |content: &Content| match content {
+            Content::Coin(_) => 10.0,
+            Content::Rock(_) => 0.5,
+            _ => 1.0,
+        };
+        assert_eq!(tracker.estimated_value(value), 4.0 * 0.5 + 2.0 * 10.0 + 1.0);
+
+        // completed goals have nothing left to gain
+        tracker.update_manual(GoalType::GetItems, Some(Content::Coin(0)), 1).unwrap();
+        tracker.update_manual(GoalType::SellItems, Some(Content::Fish(0)), 1).unwrap();
+        assert_eq!(tracker.estimated_value(value), 4.0 * 0.5 + 10.0);
+    }
+
+    #[test]
+    fn test_goal_eq() {
+        let rocks = || goal("rocks", GoalType::GetItems, Some(Content::Rock(0)), 2);
+        assert_eq!(rocks(), rocks());
+        assert_ne!(rocks(), rocks().with_priority(1));
+
+        let mut tracker = GoalTracker::new();
+        let mut other = GoalTracker::new();
+        tracker.add_goal(rocks());
+        other.add_goal(rocks());
+        // the callbacks are not compared
+        other.on_complete(Box::new(|_| {}));
+        assert_eq!(tracker, other);
+
+        tracker.update_manual(GoalType::GetItems, Some(Content::Rock(0)), 2).unwrap();
+        assert_ne!(tracker, other);
+    }
+
+    #[test]
+    fn test_len_and_clear() {
+        let mut tracker = GoalTracker::new();
+        assert!(tracker.is_empty());
+
+        tracker.add_goal(goal("rocks", GoalType::GetItems, Some(Content::Rock(0)), 2));
+        tracker.add_goal(goal("fire", GoalType::PutOutFire, Some(Content::Water(0)), 1));
+        tracker.update_manual(GoalType::PutOutFire, Some(Content::Water(0)), 1).unwrap();
+        assert_eq!(tracker.len(), 2);
+        assert!(!tracker.is_empty());
+
+        tracker.clear();
+        assert!(tracker.is_empty());
+        assert_eq!(tracker.get_completed_number(), 0);
+    }
+
+    #[test]
+    fn test_goal_progress() {
+        let mut tracker = GoalTracker::new();
+        tracker.add_goal(goal("rocks", GoalType::GetItems, Some(Content::Rock(0)), 5));
+
+        // e.g. destroying a Rock(3)
+        let progress = tracker.update_manual(GoalType::GetItems, Some(Content::Rock(0)), 3);
+        assert_eq!(
+            progress,
+            Ok(GoalProgress {
+                goal_name: "rocks".to_string(),
+                delta: 3,
+                items_left: 2,
+                just_completed: false,
+            })
+        );
+
+        // only the items needed are counted
+        let progress = tracker.update_manual(GoalType::GetItems, Some(Content::Rock(0)), 3);
+        let progress = progress.unwrap();
+        assert_eq!(progress.delta, 2);
+        assert!(progress.just_completed);
+    }
+
+    #[test]
+    fn test_explore_goal() {
+        let grass = Tile {
+            tile_type: TileType::Grass,
+            content: Content::None,
+            elevation: 0,
+        };
+        let mut tiles = vec![vec![None; 3]; 3];
+        tiles[0][0] = Some(grass.clone());
+        let mut map = BobMap::from_tiles(tiles);
+
+        let mut tracker = GoalTracker::new();
+        tracker.add_goal(goal("explore", GoalType::Explore, None, 5));
+
+        // the already known tile is not new
+        let seen = vec![(0, 0, grass.clone()), (0, 1, grass.clone()), (1, 1, grass.clone())];
+        map.update_with_diff(seen);
+        let progress = tracker.update_explored(&mut map).unwrap();
+        assert_eq!(progress.delta, 2);
+        assert_eq!(progress.items_left, 3);
+
+        // nothing new since the last update
+        assert_eq!(tracker.update_explored(&mut map).unwrap().delta, 0);
+    }
+
+    fn water_in_backpack(robot: &impl Runnable) -> usize {
+        robot.get_backpack().get_contents().get(&Content::Water(0)).copied().unwrap_or(0)
+    }
+
+    #[test]
+    fn test_put_out_fire_with_amount() {
+        // water on the right of the robot, fire below it
+        let world = TestWorld::new(3, (1, 1))
+            .with_tile((1, 2), TileType::Grass, Content::Water(5))
+            .with_tile((2, 1), TileType::Grass, Content::Fire);
+        let checked = Rc::new(RefCell::new(false));
+        let done = Rc::clone(&checked);
+
+        run_tick(world, move |robot, _, goal_tracker, world| {
+            goal_tracker.add_goal(goal("fire", GoalType::PutOutFire, Some(Content::Water(0)), 3));
+            destroy(robot, world, Direction::Right).unwrap();
+            let before = water_in_backpack(robot);
+            assert!(before >= 3);
+
+            let progress =
+                put_out_fire_with(robot, world, Direction::Down, 3, goal_tracker).unwrap();
+            let spent = before - water_in_backpack(robot);
+            assert!(spent > 0 && spent <= 3);
+            assert_eq!(progress.delta as usize, spent);
+            assert_eq!(progress.items_left as usize, 3 - spent);
+            *done.borrow_mut() = true;
+        });
+        assert!(*checked.borrow());
+    }
+
+    #[test]
+    fn test_put_out_fire_with_border() {
+        let world = TestWorld::new(3, (0, 0));
+        let checked = Rc::new(RefCell::new(false));
+        let done = Rc::clone(&checked);
+
+        run_tick(world, move |robot, _, goal_tracker, world| {
+            goal_tracker.add_goal(goal("fire", GoalType::PutOutFire, Some(Content::Water(0)), 1));
+
+            // nothing is above or on the left of the robot
+            for direction in [Direction::Up, Direction::Left] {
+                let result = put_out_fire_with(robot, world, direction, 3, goal_tracker);
+                assert!(matches!(result, Err(ActionError::Lib(LibError::OutOfBounds))));
+            }
+            assert_eq!(*goal_tracker.get_goals()[0].get_items_left(), 1);
+            *done.borrow_mut() = true;
+        });
+        assert!(*checked.borrow());
+    }
+
+    #[test]
+    fn test_sell_items_in_market_errors() {
+        // market on the right of the robot, which carries no fish
+        let world =
+            TestWorld::new(3, (0, 0)).with_tile((0, 1), TileType::Grass, Content::Market(5));
+        let checked = Rc::new(RefCell::new(false));
+        let done = Rc::clone(&checked);
+
+        run_tick(world, move |robot, _, goal_tracker, world| {
+            goal_tracker.add_goal(goal("sell", GoalType::SellItems, Some(Content::Fish(0)), 2));
+
+            let mut sell = |direction| {
+                sell_items_in_market(robot, world, Content::Fish(0), 2, direction, goal_tracker)
+            };
+            assert!(matches!(
+                sell(Direction::Right),
+                Err(ActionError::Lib(LibError::NotEnoughContentInBackPack))
+            ));
+            assert!(matches!(sell(Direction::Up), Err(ActionError::Lib(LibError::OutOfBounds))));
+            assert_eq!(*goal_tracker.get_goals()[0].get_items_left(), 2);
+            *done.borrow_mut() = true;
+        });
+        assert!(*checked.borrow());
+    }
+}
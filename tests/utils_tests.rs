@@ -0,0 +1,115 @@
+#[cfg(test)]
+mod tests {
+    use robotics_lib::interface::Direction;
+    use robotics_lib::world::tile::{Content, Tile, TileType};
+
+    use bob_lib::{
+        chebyshev_distance, direction_between, direction_in_view, manhattan_distance,
+        tile_in_view_direction,
+    };
+
+    #[test]
+    fn test_manhattan_distance() {
+        assert_eq!(manhattan_distance((0, 0), (0, 0)), 0);
+        assert_eq!(manhattan_distance((1, 2), (4, 6)), 7);
+        assert_eq!(manhattan_distance((4, 6), (1, 2)), 7);
+        assert_eq!(manhattan_distance((5, 0), (0, 5)), 10);
+    }
+
+    #[test]
+    fn test_chebyshev_distance() {
+        assert_eq!(chebyshev_distance((3, 3), (3, 3)), 0);
+        assert_eq!(chebyshev_distance((1, 2), (4, 6)), 4);
+        assert_eq!(chebyshev_distance((4, 6), (1, 2)), 4);
+        assert_eq!(chebyshev_distance((5, 0), (0, 5)), 5);
+    }
+
+    #[test]
+    fn test_direction_between() {
+        let direction = |to| direction_between((1, 1), to).map(|d| format!("{:?}", d));
+
+        assert_eq!(direction((0, 1)).as_deref(), Some("Up"));
+        assert_eq!(direction((2, 1)).as_deref(), Some("Down"));
+        assert_eq!(direction((1, 0)).as_deref(), Some("Left"));
+        assert_eq!(direction((1, 2)).as_deref(), Some("Right"));
+        assert_eq!(direction((2, 2)), None);
+        assert_eq!(direction((1, 1)), None);
+        assert_eq!(direction((1, 3)), None);
+    }
+
+    #[test]
+    fn test_tile_in_view_direction() {
+        let tile = |tile_type| {
+            Some(Tile {
+                tile_type,
+                content: Content::None,
+                elevation: 0,
+            })
+        };
+        // view of a robot in (0, 0): the first row and column are outside the map
+        let view = vec![
+            vec![None, None, None],
+            vec![None, tile(TileType::Grass), tile(TileType::Sand)],
+            vec![None, tile(TileType::Hill), tile(TileType::Grass)],
+        ];
+
+        assert!(tile_in_view_direction(&view, &Direction::Up).is_none());
+        assert!(tile_in_view_direction(&view, &Direction::Left).is_none());
+        let right = tile_in_view_direction(&view, &Direction::Right).unwrap();
+        assert_eq!(right.tile_type, TileType::Sand);
+        let down = tile_in_view_direction(&view, &Direction::Down).unwrap();
+        assert_eq!(down.tile_type, TileType::Hill);
+
+        assert!(tile_in_view_direction(&[], &Direction::Up).is_none());
+    }
+
+    #[test]
+    fn test_direction_in_view() {
+        let tile = |content| {
+            Some(Tile {
+                tile_type: TileType::Grass,
+                content,
+                elevation: 0,
+            })
+        };
+        let mut view = vec![vec![tile(Content::None); 3]; 3];
+        view[1][0] = tile(Content::Bin(0..5));
+        view[0][1] = None;
+
+        let is_bin = |tile: &Tile| matches!(tile.content, Content::Bin(_));
+        let direction = direction_in_view(&view, is_bin).map(|d| format!("{:?}", d));
+        assert_eq!(direction.as_deref(), Some("Left"));
+        assert!(direction_in_view(&view, |tile| tile.content == Content::Fire).is_none());
+        // the undiscovered tile up is skipped, down is the first grass
+        let grass = direction_in_view(&view, |tile| tile.content == Content::None);
+        assert_eq!(grass.map(|d| format!("{:?}", d)).as_deref(), Some("Down"));
+    }
+
+    #[test]
+    fn test_direction_in_view_left_right() {
+        let tile = |content| {
+            Some(Tile {
+                tile_type: TileType::Grass,
+                content,
+                elevation: 0,
+            })
+        };
+        let is_fire = |tile: &Tile| tile.content == Content::Fire;
+        let direction = |view: &[Vec<Option<Tile>>]| {
+            direction_in_view(view, is_fire).map(|d| format!("{:?}", d))
+        };
+
+        // what put_out_fire_anywhere sees with a fire on the right, then on the left
+        let mut view = vec![vec![tile(Content::None); 3]; 3];
+        view[1][2] = tile(Content::Fire);
+        assert_eq!(direction(&view).as_deref(), Some("Right"));
+        view[1][2] = tile(Content::None);
+        view[1][0] = tile(Content::Fire);
+        assert_eq!(direction(&view).as_deref(), Some("Left"));
+
+        // the fire is not adjacent
+        view[1][0] = tile(Content::None);
+        view[0][0] = tile(Content::Fire);
+        assert_eq!(direction(&view), None);
+    }
+}
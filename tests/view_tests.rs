@@ -0,0 +1,142 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    use robotics_lib::interface::{one_direction_view, Direction};
+    use robotics_lib::runner::Runnable;
+    use robotics_lib::world::tile::{Content, TileType};
+
+    use bob_lib::enhanced_map::{bob_one_direction_view, bob_radius_view, bob_view};
+
+    use crate::common::{run_tick, TestWorld};
+
+    #[test]
+    fn test_one_direction_view_down() {
+        let world = TestWorld::new(5, (1, 2)).with_tile((3, 2), TileType::Sand, Content::None);
+        let checked = Rc::new(RefCell::new(false));
+        let done = Rc::clone(&checked);
+
+        run_tick(world, move |robot, map, _, world| {
+            let (row, col) = (robot.get_coordinate().get_row(), robot.get_coordinate().get_col());
+            assert!(map.get_map()[row + 2][col].0.is_none());
+
+            let view = bob_one_direction_view(robot, world, Direction::Down, 2, map).unwrap();
+            let below = map.get_map()[row + 2][col].0.as_ref();
+            assert!(below.is_some_and(|tile| tile.tile_type == TileType::Sand));
+            assert!(view.iter().flatten().all(|(_, x, _)| *x > row));
+            *done.borrow_mut() = true;
+        });
+        assert!(*checked.borrow());
+    }
+
+    #[test]
+    fn test_one_direction_view_border() {
+        let world = TestWorld::new(5, (0, 0)).with_tile((0, 2), TileType::Sand, Content::None);
+        let checked = Rc::new(RefCell::new(false));
+        let done = Rc::clone(&checked);
+
+        run_tick(world, move |robot, map, _, world| {
+            // nothing is above or on the left of the robot, no tile can be placed there
+            for direction in [Direction::Up, Direction::Left] {
+                if let Ok(view) = bob_one_direction_view(robot, world, direction, 2, map) {
+                    assert!(view.iter().all(|row| row.is_empty()));
+                }
+            }
+            let view = bob_one_direction_view(robot, world, Direction::Right, 2, map).unwrap();
+            assert!(view.iter().flatten().all(|(_, _, y)| *y > 0));
+            assert!(map.get_tile((0, 2)).is_some_and(|tile| tile.tile_type == TileType::Sand));
+            *done.borrow_mut() = true;
+        });
+        assert!(*checked.borrow());
+    }
+
+    #[test]
+    fn test_radius_view() {
+        let world = TestWorld::new(7, (3, 3)).with_tile((1, 1), TileType::Sand, Content::None);
+        let checked = Rc::new(RefCell::new(false));
+        let done = Rc::clone(&checked);
+
+        run_tick(world, move |robot, map, _, world| {
+            let view = bob_radius_view(robot, world, 2, map).unwrap();
+
+            // the whole square is discovered, corners included
+            assert_eq!(view.len(), 5);
+            assert!(view.iter().all(|row| row.len() == 5));
+            assert!(view.iter().flatten().all(|(tile, _, _)| tile.is_some()));
+            assert_eq!((view[0][0].1, view[0][0].2), (1, 1));
+            assert!(map.get_tile((1, 1)).is_some_and(|tile| tile.tile_type == TileType::Sand));
+            assert!(map.last_diff().contains(&(1, 1)));
+            *done.borrow_mut() = true;
+        });
+        assert!(*checked.borrow());
+    }
+
+    #[test]
+    fn test_refresh_counts_discovered() {
+        let world = TestWorld::new(5, (0, 0));
+        let checked = Rc::new(RefCell::new(false));
+        let done = Rc::clone(&checked);
+
+        run_tick(world, move |robot, map, _, world| {
+            map.take_discovered();
+            let before = map.discovered_count();
+
+            // the tiles are revealed behind the back of the map, only refresh sees them
+            one_direction_view(robot, world, Direction::Down, 3).unwrap();
+            assert_eq!(map.discovered_count(), before);
+            map.refresh(world);
+
+            let revealed = map.discovered_count() - before;
+            assert!(revealed > 0);
+            assert_eq!(map.take_discovered(), revealed);
+            *done.borrow_mut() = true;
+        });
+        assert!(*checked.borrow());
+    }
+
+    #[test]
+    fn test_view_corner() {
+        let world = TestWorld::new(5, (0, 0)).with_tile((1, 1), TileType::Sand, Content::None);
+        let checked = Rc::new(RefCell::new(false));
+        let done = Rc::clone(&checked);
+
+        run_tick(world, move |robot, map, _, world| {
+            let view = bob_view(robot, world, map);
+
+            // only the lower-right quadrant of the 3x3 view is inside the map
+            let coordinates: Vec<Vec<(usize, usize)>> = view
+                .iter()
+                .map(|row| row.iter().map(|(_, x, y)| (*x, *y)).collect())
+                .collect();
+            assert_eq!(coordinates, vec![vec![(0, 0), (0, 1)], vec![(1, 0), (1, 1)]]);
+            assert!(view[1][1].0.as_ref().is_some_and(|tile| tile.tile_type == TileType::Sand));
+            assert!(map.get_tile((1, 1)).is_some_and(|tile| tile.tile_type == TileType::Sand));
+            *done.borrow_mut() = true;
+        });
+        assert!(*checked.borrow());
+    }
+
+    #[test]
+    fn test_relative_round_trip_edge() {
+        for spawn in [(0, 0), (2, 2)] {
+            let checked = Rc::new(RefCell::new(false));
+            let done = Rc::clone(&checked);
+
+            run_tick(TestWorld::new(3, spawn), move |robot, map, _, world| {
+                map.refresh(world);
+                for rel in (0..3).flat_map(|x| (0..3).map(move |y| (x, y))) {
+                    // the indices pointing outside the map have no absolute coordinates
+                    if let Some(absolute) = map.to_absolute(robot, rel) {
+                        assert_eq!(map.to_relative(robot, absolute), Some(rel));
+                    }
+                }
+                assert_eq!(map.to_relative(robot, (usize::MAX, usize::MAX)), None);
+                *done.borrow_mut() = true;
+            });
+            assert!(*checked.borrow());
+        }
+    }
+}